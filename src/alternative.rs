@@ -14,10 +14,15 @@ pub trait Alternative<A: Clone>: Applicative<A> {
     type Alternative<B: Clone>: Alternative<B, Alternative<A> = Self>;
     /// Value representing failure/emptiness/nothingness.
     #[must_use]
-    fn empty() -> Self;
+    fn empty() -> Self
+    where
+        A: 'static;
     /// Return a successful result if we have one.
+    /// `make_other` is `'static` so that lazy alternatives (e.g. `Stream`) can hold onto it until they need it.
     #[must_use]
-    fn either<F: FnOnce() -> Self>(self, make_other: F) -> Self;
+    fn either<F: FnOnce() -> Self + 'static>(self, make_other: F) -> Self
+    where
+        A: 'static;
     /// Return empty or a trivial value based on a predicate.
     #[inline(always)]
     #[must_use]
@@ -36,14 +41,17 @@ pub trait Alternative<A: Clone>: Applicative<A> {
 /// Value representing failure/emptiness/nothingness.
 #[inline(always)]
 #[must_use]
-pub fn empty<AA: Alternative<A>, A: Clone>() -> AA {
+pub fn empty<AA: Alternative<A>, A: Clone + 'static>() -> AA {
     AA::empty()
 }
 
 /// Return a successful result if we have one.
 #[inline(always)]
 #[must_use]
-pub fn either<AA: Alternative<A>, A: Clone, F: FnOnce() -> AA>(f1: F, f2: F) -> AA {
+pub fn either<AA: Alternative<A>, A: Clone + 'static, F: FnOnce() -> AA + 'static>(
+    f1: F,
+    f2: F,
+) -> AA {
     f1().either(f2)
 }
 
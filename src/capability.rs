@@ -0,0 +1,200 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Capability traits, for code generic over any monad with some effect rather than over one concrete stack.
+//!
+//! Every monad that has an effect implements its trait directly, and every transformer passes along its base's capabilities,
+//! so e.g. `MonadState::get` works no matter how deep the state layer sits.
+//!
+//! Like Haskell's `mtl`, each stack has at most one of each capability (at a given type): a transformer with its own state hides any state beneath it.
+//! # Use
+//! ```rust
+//! use rsmonad::prelude::*;
+//! # #[cfg(feature = "std")]
+//! # {
+//! // Hand out fresh IDs from any monad with a `u8` counter in it:
+//! fn fresh<M: MonadState<u8>>() -> M::Of<u8> {
+//!     M::bind_in(M::get(), |n: u8| M::bind_in(M::put(n + 1), move |()| M::consume_in(n)))
+//! }
+//! assert_eq!(fresh::<State<(), u8>>().run_state(5), (5, 6));
+//! assert_eq!(
+//!     fresh::<MaybeT<(), State<(), u8>>>().run_maybe_t().run_state(5),
+//!     (Just(5), 6),
+//! );
+//! assert_eq!(
+//!     fresh::<WriterT<(), SumU8, StateT<(), u8, Option<()>>>>()
+//!         .run_writer_t()
+//!         .run_state_t(5),
+//!     Some(((5, SumU8(0)), 6)),
+//! );
+//! # }
+//! ```
+
+use crate::prelude::*;
+
+/// Original Haskell definition:
+/// ```haskell
+/// class Monad m => MonadState s m | m -> s where
+/// get :: m s
+/// put :: s -> m ()
+/// ```
+pub trait MonadState<S: Clone + 'static>: MonadKind {
+    /// Read the current state without changing it.
+    fn get() -> Self::Of<S>;
+    /// Replace the current state.
+    fn put(s: S) -> Self::Of<()>;
+    /// Read some function of the current state without changing it.
+    #[inline(always)]
+    fn gets<A: Clone + 'static, F: FnOnce(&S) -> A + Clone + 'static>(f: F) -> Self::Of<A> {
+        Self::bind_in(Self::get(), move |s| Self::consume_in(f(&s)))
+    }
+    /// Replace the current state with some function of it.
+    #[inline(always)]
+    fn modify<F: FnOnce(S) -> S + Clone + 'static>(f: F) -> Self::Of<()> {
+        Self::bind_in(Self::get(), move |s| Self::put(f(s)))
+    }
+}
+
+/// Original Haskell definition:
+/// ```haskell
+/// class Monad m => MonadReader r m | m -> r where
+/// ask   :: m r
+/// local :: (r -> r) -> m a -> m a
+/// ```
+pub trait MonadReader<R: Clone + 'static>: MonadKind {
+    /// Read the whole environment.
+    fn ask() -> Self::Of<R>;
+    /// Run a computation in a modified copy of the environment.
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: Self::Of<A>,
+    ) -> Self::Of<A>;
+    /// Read some function of the environment.
+    #[inline(always)]
+    fn asks<A: Clone + 'static, F: FnOnce(&R) -> A + Clone + 'static>(f: F) -> Self::Of<A> {
+        Self::bind_in(Self::ask(), move |r| Self::consume_in(f(&r)))
+    }
+}
+
+/// Original Haskell definition:
+/// ```haskell
+/// class (Monoid w, Monad m) => MonadWriter w m | m -> w where
+/// tell   :: w -> m ()
+/// listen :: m a -> m (a, w)
+/// ```
+pub trait MonadWriter<W: Monoid + Clone + 'static>: MonadKind {
+    /// Log a value.
+    fn tell(w: W) -> Self::Of<()>;
+    /// Return what a computation logged alongside its value (and keep it in the log too).
+    fn listen<A: Clone + 'static>(m: Self::Of<A>) -> Self::Of<(A, W)>;
+}
+
+/// Original Haskell definition:
+/// ```haskell
+/// class Monad m => MonadError e m | m -> e where
+/// throwError :: e -> m a
+/// catchError :: m a -> (e -> m a) -> m a
+/// ```
+pub trait MonadError<E: Clone + 'static>: MonadKind {
+    /// Fail, skipping every effect after this one.
+    fn throw<A: Clone + 'static>(e: E) -> Self::Of<A>;
+    /// Recover from a failure by running another computation with its reason.
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> Self::Of<A> + Clone + 'static>(
+        m: Self::Of<A>,
+        handler: F,
+    ) -> Self::Of<A>;
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    fn fresh<M: MonadState<u8>>() -> M::Of<u8> {
+        M::bind_in(M::get(), |n: u8| {
+            M::bind_in(M::put(n + 1), move |()| M::consume_in(n))
+        })
+    }
+
+    fn twice<M: MonadState<u8>>() -> M::Of<(u8, u8)> {
+        M::bind_in(fresh::<M>(), |a| {
+            M::bind_in(fresh::<M>(), move |b| M::consume_in((a, b)))
+        })
+    }
+
+    #[test]
+    fn state_through_every_transformer() {
+        type Base = State<(), u8>;
+        assert_eq!(twice::<Base>().run_state(0), ((0, 1), 2));
+        assert_eq!(
+            twice::<MaybeT<(), Base>>().run_maybe_t().run_state(0),
+            (Just((0, 1)), 2)
+        );
+        assert_eq!(
+            twice::<HazardT<(), Base, ()>>().run_hazard_t().run_state(0),
+            (Success((0, 1)), 2)
+        );
+        assert_eq!(
+            twice::<ReaderT<(), (), Base>>()
+                .run_reader_t(&())
+                .run_state(0),
+            ((0, 1), 2)
+        );
+        assert_eq!(
+            twice::<WriterT<(), SumU8, Base>>()
+                .run_writer_t()
+                .run_state(0),
+            (((0, 1), SumU8(0)), 2)
+        );
+        assert_eq!(
+            twice::<ListT<(), Base>>().run_list_t().run_state(0),
+            (vec![(0, 1)], 2)
+        );
+        assert_eq!(
+            twice::<MaybeT<(), ReaderT<(), (), StateT<(), u8, Option<()>>>>>()
+                .run_maybe_t()
+                .run_reader_t(&())
+                .run_state_t(0),
+            Some((Just((0, 1)), 2))
+        );
+    }
+
+    #[test]
+    fn reader_through_state() {
+        type Stack = StateT<(), u8, Reader<(), u8>>;
+        let m = Stack::bind_in(Stack::asks(|r: &u8| r * 2), |x| {
+            Stack::bind_in(Stack::put(x), move |()| {
+                Stack::local(|r: &u8| r + 1, Stack::ask())
+            })
+        });
+        assert_eq!(m.run_state_t(0).run_reader(&10), (11, 20));
+    }
+
+    #[test]
+    fn writer_through_maybe() {
+        type Stack = MaybeT<(), Writer<(), List<u8>>>;
+        let m = Stack::listen(Stack::bind_in(Stack::tell(list![1]), |()| {
+            Stack::tell(list![2])
+        }));
+        assert_eq!(
+            m.run_maybe_t().run_writer(),
+            (Just(((), list![1, 2])), list![1, 2])
+        );
+    }
+
+    #[test]
+    fn error_through_state() {
+        type Stack = StateT<(), u8, Hazard<(), &'static str>>;
+        let failing = Stack::bind_in(Stack::put(1), |()| Stack::throw("oops"));
+        assert_eq!(failing.clone().run_state_t(0), Failure("oops"));
+        // State changes before a failure are rolled back by `catch`.
+        let recovered = Stack::catch(failing, |e: &str| {
+            Stack::put(e.len().try_into().unwrap_or(u8::MAX))
+        });
+        assert_eq!(recovered.run_state_t(0), Success(((), 4)));
+    }
+}
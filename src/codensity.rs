@@ -0,0 +1,227 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Codensity` monad transformer.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::rc::Rc;
+
+/// Any monad `M` (written as e.g. `Vec<()>` for `Vec<_>`; see `MonadKind`), but with every `>>` reassociated to the right, eventually producing an `M<R>`.
+///
+/// Under the hood, just `Cont` with `M<R>` as its result: `>>` only composes continuations, and `M`'s own bind runs once per `lift`ed effect.
+/// Monads whose bind walks a whole tree (e.g. `Free` or `Eff`) take quadratic time on left-nested `>>` chains like `fold`s;
+/// `lift` each effect into `Codensity`, build the program as usual, and `lower` it at the end to make them linear again.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+///
+/// #[derive(Clone)]
+/// struct Tick<Next>(Next);
+/// functor! {
+///     kind Tick<A>:
+///
+///     fn fmap(self, f) {
+///         Tick(f(self.0))
+///     }
+/// }
+///
+/// # fn main() {
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// type Clock = Free<(), Tick<()>>;
+///
+/// // Count a thousand ticks, folding from the left without paying for it:
+/// let ticks = (0..1_000).fold(consume(0), |acc: Codensity<u16, Clock, u16>, _| {
+///     acc >> |n| Codensity::lift(lift_f(Tick(n + 1)))
+/// });
+/// assert_eq!(ticks.lower().run(|Tick(next)| next), 1_000);
+/// # }
+/// # }
+/// ```
+pub struct Codensity<A: Clone, M: MonadKind, R: Clone>(
+    Rc<dyn Fn(Continuation<A, M::Of<R>>) -> M::Of<R>>,
+);
+
+monad! {
+    Codensity<A, M: MonadKind + 'static, R: Clone + 'static>:
+
+    fn consume(a) {
+        Self::new(move |k| k(a.clone()))
+    }
+
+    fn bind(self, f) {
+        Codensity::new(move |k| {
+            let f = f.clone();
+            (self.0)(Rc::new(move |a| (f.clone()(a).0)(Rc::clone(&k))))
+        })
+    }
+
+    fn observe(m: Codensity<u64, Option<()>, u64>) {
+        m.lower()
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static, R: Clone + 'static> MonadTrans<A, M>
+    for Codensity<A, M, R>
+{
+    #[inline(always)]
+    fn lift(m: M::Of<A>) -> Self {
+        Self::new(move |k| M::bind_in(m.clone(), move |a| k(a)))
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static, R: Clone + 'static> Codensity<A, M, R> {
+    /// Wrap a function that takes the rest of the computation as its argument.
+    #[inline(always)]
+    pub fn new<F: Fn(Continuation<A, M::Of<R>>) -> M::Of<R> + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run this computation with a final continuation into the underlying monad.
+    #[inline(always)]
+    pub fn run_codensity<K: Fn(A) -> M::Of<R> + 'static>(self, k: K) -> M::Of<R> {
+        (self.0)(Rc::new(k))
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static> Codensity<A, M, A> {
+    /// Turn this back into the underlying monad, with all its binds associated to the right.
+    #[inline(always)]
+    pub fn lower(self) -> M::Of<A> {
+        self.run_codensity(M::consume_in)
+    }
+}
+
+impl<A: Clone, M: MonadKind, R: Clone> Clone for Codensity<A, M, R> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A: Clone, M: MonadKind, R: Clone> core::fmt::Debug for Codensity<A, M, R> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Codensity(<function>)")
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static, R: Clone + 'static> quickcheck::Arbitrary
+    for Codensity<A, M, R>
+where
+    M::Of<A>: quickcheck::Arbitrary,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self::lift(M::Of::<A>::arbitrary(g))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod over_std {
+    //! Laws again, this time over some more interesting base monads.
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    mod over_list {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: Codensity<u64, List<()>, u64>) {
+                m.lower()
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: Codensity<u64, List<()>, u64>) {
+                m.lower()
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: Codensity<u64, List<()>, u64>) {
+                m.lower()
+            }
+        );
+    }
+
+    mod over_state {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: Codensity<u64, State<(), u64>, u64>, s: u64) {
+                m.lower().run_state(s)
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: Codensity<u64, State<(), u64>, u64>, s: u64) {
+                m.lower().run_state(s)
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: Codensity<u64, State<(), u64>, u64>, s: u64) {
+                m.lower().run_state(s)
+            }
+        );
+    }
+
+    #[test]
+    fn left_nested_binds_are_linear() {
+        use core::cell::Cell;
+
+        // A `Free` instruction that counts every time `Free`'s bind has to walk past it.
+        #[derive(Clone)]
+        struct Tick<Next>(Next);
+        impl<X: Clone> FunctorKind for Tick<X> {
+            type Of<A: Clone> = Tick<A>;
+            fn fmap_in<
+                A: Clone + 'static,
+                B: Clone + 'static,
+                F: FnOnce(A) -> B + Clone + 'static,
+            >(
+                fa: Tick<A>,
+                f: F,
+            ) -> Tick<B> {
+                STEPS.with(|steps| steps.set(steps.get() + 1));
+                Tick(f(fa.0))
+            }
+        }
+        type Clock = Free<(), Tick<()>>;
+        thread_local! {
+            static STEPS: Cell<usize> = const { Cell::new(0) };
+        }
+        fn tick(n: u64) -> Free<u64, Tick<()>> {
+            lift_f(Tick(n + 1))
+        }
+        fn naive(n: u64) -> (u64, usize) {
+            STEPS.with(|steps| steps.set(0));
+            let program = (0..n).fold(consume(0), |acc: Free<u64, Tick<()>>, _| acc >> tick);
+            (program.run(|Tick(next)| next), STEPS.with(Cell::get))
+        }
+        fn codensity(n: u64) -> (u64, usize) {
+            STEPS.with(|steps| steps.set(0));
+            let program = (0..n).fold(consume(0), |acc: Codensity<u64, Clock, u64>, _| {
+                acc >> |x| Codensity::lift(tick(x))
+            });
+            (
+                program.lower().run(|Tick(next)| next),
+                STEPS.with(Cell::get),
+            )
+        }
+
+        // Same answers either way...
+        let (naive_small, naive_small_steps) = naive(200);
+        let (naive_large, naive_large_steps) = naive(400);
+        let (fast_small, fast_small_steps) = codensity(200);
+        let (fast_large, fast_large_steps) = codensity(400);
+        assert_eq!((naive_small, naive_large), (200, 400));
+        assert_eq!((fast_small, fast_large), (200, 400));
+
+        // ...but doubling the length quadruples the work without `Codensity` and only doubles it with.
+        assert_eq!(naive_small_steps, 20_100); // 1 + 2 + ... + 200
+        assert_eq!(naive_large_steps, 80_200); // 1 + 2 + ... + 400
+        assert_eq!(fast_small_steps, 400); // once in `lift_f` and once in `lower`
+        assert_eq!(fast_large_steps, 800);
+    }
+}
@@ -0,0 +1,62 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Const` functor.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use core::marker::PhantomData;
+
+/// Holds a `C` and pretends to hold an `A`: mapping over it does nothing at all.
+///
+/// As an applicative, `*` combines the `C`s with `Monoid::combine`, which is exactly what a traversal needs to collect values instead of rebuilding a structure.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// let c: Const<u8, SumU8> = Const::new(SumU8(3));
+/// assert_eq!((c % |x: u8| x + 1).get_const(), SumU8(3));
+/// let f: Const<fn(u8) -> u8, SumU8> = Const::new(SumU8(4));
+/// assert_eq!((c * f).get_const(), SumU8(7));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Const<A, C>(C, PhantomData<A>);
+
+applicative! {
+    Const<A, C: Monoid + 'static>:
+
+    fn consume(_a) {
+        Self::new(unit())
+    }
+
+    fn tie(self, af) {
+        Const::new(self.0.combine(af.0))
+    }
+
+    fn observe(m: Const<u64, SumU8>) {
+        m.get_const()
+    }
+}
+
+impl<A, C> Const<A, C> {
+    /// Wrap a value, pretending it's an `A`.
+    #[inline(always)]
+    pub const fn new(c: C) -> Self {
+        Self(c, PhantomData)
+    }
+    /// Unwrap the value.
+    #[inline(always)]
+    pub fn get_const(self) -> C {
+        self.0
+    }
+}
+
+impl<A: Clone + 'static, C: quickcheck::Arbitrary> quickcheck::Arbitrary for Const<A, C> {
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self::new(C::arbitrary(g))
+    }
+}
@@ -0,0 +1,118 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Cont` (continuation) monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::rc::Rc;
+
+/// Continuation-passing style: instead of returning an `A`, take the rest of the computation (`A -> R`) and decide whether and how to call it.
+/// Under the hood, just a function `(A -> R) -> R`.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "alloc")] {
+/// // Multiply everything, but bail out as soon as we see a zero:
+/// fn product(xs: &'static [u64]) -> Cont<u64, u64> {
+///     Cont::call_cc(move |exit: Escape<u64, u64, u64>| {
+///         xs.iter().fold(consume(1), move |acc: Cont<u64, u64>, &x| {
+///             let exit = exit.clone();
+///             acc >> move |p| if x == 0 { exit(0) } else { consume(p * x) }
+///         })
+///     })
+/// }
+/// assert_eq!(product(&[1, 2, 3, 4]).eval_cont(), 24);
+/// assert_eq!(product(&[1, 2, 0, 4]).eval_cont(), 0);
+/// # }
+/// ```
+pub struct Cont<A, R>(Rc<dyn Fn(Continuation<A, R>) -> R>);
+
+/// The rest of a computation: what to do with an `A` once we have one.
+pub type Continuation<A, R> = Rc<dyn Fn(A) -> R>;
+
+/// Escape continuation handed out by `Cont::call_cc`: calling it with an `A` abandons everything after it and returns `A` from `call_cc` instead.
+pub type Escape<A, B, R> = Rc<dyn Fn(A) -> Cont<B, R>>;
+
+monad! {
+    Cont<A, R: 'static>:
+
+    fn consume(a) {
+        Self::new(move |k| k(a.clone()))
+    }
+
+    fn bind(self, f) {
+        Cont::new(move |k| {
+            let f = f.clone();
+            (self.0)(Rc::new(move |a| (f.clone()(a).0)(Rc::clone(&k))))
+        })
+    }
+
+    fn observe(m: Cont<u64, u64>) {
+        m.run_cont(reverse)
+    }
+}
+
+impl<A: 'static, R: 'static> Cont<A, R> {
+    /// Wrap a function that takes the rest of the computation as its argument.
+    #[inline(always)]
+    pub fn new<F: Fn(Continuation<A, R>) -> R + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run this computation with a final continuation.
+    #[inline(always)]
+    pub fn run_cont<K: Fn(A) -> R + 'static>(self, k: K) -> R {
+        (self.0)(Rc::new(k))
+    }
+    /// Call with current continuation: hand `f` an escape hatch that, when called, jumps straight out of `call_cc` with its argument.
+    #[inline(always)]
+    pub fn call_cc<B: 'static, F: Fn(Escape<A, B, R>) -> Self + 'static>(f: F) -> Self
+    where
+        A: Clone,
+    {
+        Self::new(move |k| {
+            let outer = Rc::clone(&k);
+            let escape: Escape<A, B, R> = Rc::new(move |a| {
+                let exit = Rc::clone(&outer);
+                Cont::new(move |_| exit(a.clone()))
+            });
+            (f(escape).0)(k)
+        })
+    }
+}
+
+impl<A: 'static> Cont<A, A> {
+    /// Run this computation with the identity continuation.
+    #[inline(always)]
+    pub fn eval_cont(self) -> A {
+        self.run_cont(core::convert::identity)
+    }
+}
+
+impl<A, R> Clone for Cont<A, R> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A, R> core::fmt::Debug for Cont<A, R> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Cont(<function>)")
+    }
+}
+
+impl<A: quickcheck::Arbitrary, R: quickcheck::Arbitrary> quickcheck::Arbitrary for Cont<A, R> {
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // Either pass a value along to the rest of the computation or ignore it and bail out.
+        let a = A::arbitrary(g);
+        let abort = Option::<R>::arbitrary(g);
+        Self::new(move |k| abort.clone().unwrap_or_else(|| k(a.clone())))
+    }
+}
@@ -0,0 +1,463 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Eff` (freer) monad with an open row of algebraic effects.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::{rc::Rc, vec::Vec};
+use core::{convert::Infallible, marker::PhantomData};
+
+/// A program that can make requests from any of the effects in its row `R` (see `effects!`), describing what to do without doing any of it.
+///
+/// Unlike `Free`, effects are plain types (usually enums) rather than functors, and programs using several of them don't need nested coproducts:
+/// `Eff::send` finds an effect anywhere in the row, and each handler discharges the first effect in the row, leaving a program with one fewer.
+/// Once every effect is handled, `run` returns the value; or `run_m` interprets the last one into any monad, e.g. `Io`.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// type Bank = effects![ReaderEff<u8>, StateEff<u8>, HazardEff<&'static str>];
+///
+/// // Take some money out, less a fee from the environment:
+/// fn withdraw(amount: u8) -> Eff<u8, Bank> {
+///     Eff::get() >> move |balance: u8| {
+///         if amount > balance {
+///             Eff::throw("insufficient funds")
+///         } else {
+///             Eff::put(balance - amount) >> move |()| Eff::ask() % move |fee: u8| amount - fee
+///         }
+///     }
+/// }
+///
+/// let run = |balance| withdraw(30).handle_reader(1).handle_state(balance).handle_hazard().run();
+/// assert_eq!(run(100), Success((29, 70)));
+/// assert_eq!(run(10), Failure("insufficient funds"));
+/// # }
+/// ```
+pub enum Eff<A: Clone, R: Row> {
+    /// A finished program: no more requests, just a value.
+    Done(A),
+    /// A request for one of the effects in the row, holding the rest of the program.
+    Request(R::Union<Self>),
+}
+
+monad! {
+    Eff<A, R: Row>:
+
+    fn consume(a) {
+        Eff::Done(a)
+    }
+
+    fn bind(self, f) {
+        match self {
+            Eff::Done(a) => f(a),
+            Eff::Request(u) => Eff::Request(R::map_union(u, move |m: Eff<A, R>| m.bind(f.clone()))),
+        }
+    }
+
+    fn observe(m: Eff<u64, Cons<StateEff<u64>, Nil>>, s: u64) {
+        m.handle_state(s).run()
+    }
+}
+
+/// Something a program can ask for, answered by whichever handler discharges it.
+/// Every request in an effect gets the same type of answer (split effects up otherwise).
+pub trait Effect: Clone + 'static {
+    /// What the handler hands back to the program.
+    type Answer: Clone + 'static;
+}
+
+/// Type-level list of effects: `Nil` or `Cons<E, Rest>`, usually written with `effects!`.
+pub trait Row: 'static {
+    /// A request for any one of these effects, along with what to do with its answer (which eventually produces a `K`).
+    type Union<K>: Clone;
+    /// Do something else with whatever the continuation in a request produces.
+    fn map_union<K: 'static, L: 'static, F: Fn(K) -> L + Clone + 'static>(
+        u: Self::Union<K>,
+        f: F,
+    ) -> Self::Union<L>;
+}
+
+/// Proof that a row includes the effect `E` (at position `I`, which is always inferred).
+pub trait Member<E: Effect, I>: Row {
+    /// Wrap a request for `E` and what to do with its answer.
+    fn inject<K: 'static>(e: E, k: Rc<dyn Fn(E::Answer) -> K>) -> Self::Union<K>;
+}
+
+/// Empty row of effects.
+#[allow(clippy::exhaustive_enums)]
+pub enum Nil {}
+
+/// Row of effects with `E` first and `Rest` after.
+#[allow(clippy::exhaustive_structs)]
+pub struct Cons<E, Rest>(PhantomData<(E, Rest)>);
+
+/// A request in an empty row of effects, which can't exist.
+#[allow(clippy::exhaustive_structs)]
+pub struct Never<K>(Infallible, PhantomData<K>);
+
+/// A request in a nonempty row of effects.
+pub enum Union<E: Effect, Rest: Row, K> {
+    /// A request for the first effect, along with what to do with its answer.
+    Head(E, Rc<dyn Fn(E::Answer) -> K>),
+    /// A request for one of the rest of the effects.
+    Tail(Rest::Union<K>),
+}
+
+/// Position of an effect at the front of a row.
+#[allow(clippy::exhaustive_enums)]
+pub enum Here {}
+
+/// Position of an effect somewhere after the front of a row.
+#[allow(clippy::exhaustive_structs)]
+pub struct There<I>(PhantomData<I>);
+
+/// Build a row of effects (see `Eff`).
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// let _: Eff<(), effects![StateEff<u8>, ReaderEff<bool>]> = consume(());
+/// let _: Eff<(), Cons<StateEff<u8>, Cons<ReaderEff<bool>, Nil>>> = consume(());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! effects {
+    () => { $crate::prelude::Nil };
+    ($e:ty $(, $rest:ty)* $(,)?) => { $crate::prelude::Cons<$e, $crate::effects![$($rest),*]> };
+}
+pub use effects;
+
+impl Row for Nil {
+    type Union<K> = Never<K>;
+    #[inline(always)]
+    fn map_union<K: 'static, L: 'static, F: Fn(K) -> L + Clone + 'static>(
+        u: Never<K>,
+        _f: F,
+    ) -> Never<L> {
+        match u.0 {}
+    }
+}
+
+impl<E: Effect, Rest: Row> Row for Cons<E, Rest> {
+    type Union<K> = Union<E, Rest, K>;
+    #[inline]
+    fn map_union<K: 'static, L: 'static, F: Fn(K) -> L + Clone + 'static>(
+        u: Union<E, Rest, K>,
+        f: F,
+    ) -> Union<E, Rest, L> {
+        match u {
+            Union::Head(e, k) => Union::Head(e, Rc::new(move |answer| f(k(answer)))),
+            Union::Tail(rest) => Union::Tail(Rest::map_union(rest, f)),
+        }
+    }
+}
+
+impl<E: Effect, Rest: Row> Member<E, Here> for Cons<E, Rest> {
+    #[inline(always)]
+    fn inject<K: 'static>(e: E, k: Rc<dyn Fn(E::Answer) -> K>) -> Union<E, Rest, K> {
+        Union::Head(e, k)
+    }
+}
+
+impl<E: Effect, F: Effect, Rest: Member<E, I>, I> Member<E, There<I>> for Cons<F, Rest> {
+    #[inline(always)]
+    fn inject<K: 'static>(e: E, k: Rc<dyn Fn(E::Answer) -> K>) -> Union<F, Rest, K> {
+        Union::Tail(Rest::inject(e, k))
+    }
+}
+
+/// State effect: read or replace a value of type `S`, discharged by `Eff::handle_state`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, QuickCheck)]
+pub enum StateEff<S> {
+    /// Read the current state.
+    Get,
+    /// Replace the current state (answered with the new state).
+    Put(S),
+}
+
+impl<S: Clone + 'static> Effect for StateEff<S> {
+    type Answer = S;
+}
+
+/// Reader effect: its only request is to read an environment of type `R`, discharged by `Eff::handle_reader`.
+#[allow(clippy::exhaustive_structs)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ReaderEff<R>(PhantomData<R>);
+
+impl<R: Clone + 'static> Effect for ReaderEff<R> {
+    type Answer = R;
+}
+
+/// Hazard effect: fail with a reason of type `E`, discharged by `Eff::handle_hazard`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, QuickCheck)]
+pub enum HazardEff<E> {
+    /// Fail, skipping the rest of the program.
+    Throw(E),
+}
+
+impl<E: Clone + 'static> Effect for HazardEff<E> {
+    type Answer = Infallible;
+}
+
+impl<A: Clone + 'static, R: Row> Eff<A, R> {
+    /// Make a request from any effect in the row, returning its answer.
+    #[inline(always)]
+    pub fn send<E: Effect<Answer = A>, I>(e: E) -> Self
+    where
+        R: Member<E, I>,
+    {
+        Self::Request(R::inject(e, Rc::new(Self::Done)))
+    }
+    /// Fail with a reason, skipping the rest of the program.
+    #[inline(always)]
+    pub fn throw<E: Clone + 'static, I>(e: E) -> Self
+    where
+        R: Member<HazardEff<E>, I>,
+    {
+        Eff::<Infallible, R>::send(HazardEff::Throw(e)).fmap(|never| match never {})
+    }
+}
+
+impl<S: Clone + 'static, R: Row> Eff<S, R> {
+    /// Read the current state.
+    #[inline(always)]
+    #[must_use]
+    pub fn get<I>() -> Self
+    where
+        R: Member<StateEff<S>, I>,
+    {
+        Self::send(StateEff::Get)
+    }
+    /// Read the whole environment.
+    #[inline(always)]
+    #[must_use]
+    pub fn ask<I>() -> Self
+    where
+        R: Member<ReaderEff<S>, I>,
+    {
+        Self::send(ReaderEff(PhantomData))
+    }
+}
+
+impl<R: Row> Eff<(), R> {
+    /// Replace the current state.
+    #[inline(always)]
+    pub fn put<S: Clone + 'static, I>(s: S) -> Self
+    where
+        R: Member<StateEff<S>, I>,
+    {
+        Eff::<S, R>::send(StateEff::Put(s)).fmap(|_| ())
+    }
+}
+
+impl<A: Clone + 'static, E: Effect, Rest: Row> Eff<A, Cons<E, Rest>> {
+    /// Discharge the first effect by turning each of its requests into a program using the rest of the effects.
+    #[inline]
+    pub fn interpret<H: Fn(E) -> Eff<E::Answer, Rest> + Clone + 'static>(
+        self,
+        handler: H,
+    ) -> Eff<A, Rest> {
+        // Loop through requests answered right away, so long programs don't use up the stack:
+        let mut program = self;
+        loop {
+            match program {
+                Self::Done(a) => return Eff::Done(a),
+                Self::Request(Union::Head(e, k)) => match handler(e) {
+                    Eff::Done(answer) => program = k(answer),
+                    pending @ Eff::Request(_) => {
+                        return pending.bind(move |answer| k(answer).interpret(handler))
+                    }
+                },
+                Self::Request(Union::Tail(u)) => {
+                    return Eff::Request(Rest::map_union(u, move |m: Self| {
+                        m.interpret(handler.clone())
+                    }))
+                }
+            }
+        }
+    }
+}
+
+impl<A: Clone + 'static, S: Clone + 'static, Rest: Row> Eff<A, Cons<StateEff<S>, Rest>> {
+    /// Discharge the state effect, starting from `s` and returning the final state alongside the value.
+    #[inline]
+    pub fn handle_state(self, s: S) -> Eff<(A, S), Rest> {
+        // As in `interpret`, loop rather than recurse through the state's own requests:
+        let (mut program, mut state) = (self, s);
+        loop {
+            match program {
+                Self::Done(a) => return Eff::Done((a, state)),
+                Self::Request(Union::Head(StateEff::Get, k)) => program = k(state.clone()),
+                Self::Request(Union::Head(StateEff::Put(t), k)) => {
+                    program = k(t.clone());
+                    state = t;
+                }
+                Self::Request(Union::Tail(u)) => {
+                    return Eff::Request(Rest::map_union(u, move |m: Self| {
+                        m.handle_state(state.clone())
+                    }))
+                }
+            }
+        }
+    }
+}
+
+impl<A: Clone + 'static, R: Clone + 'static, Rest: Row> Eff<A, Cons<ReaderEff<R>, Rest>> {
+    /// Discharge the reader effect, answering every request with `r`.
+    #[inline]
+    pub fn handle_reader(self, r: R) -> Eff<A, Rest> {
+        self.interpret(move |_| consume(r.clone()))
+    }
+}
+
+impl<A: Clone + 'static, E: Clone + 'static, Rest: Row> Eff<A, Cons<HazardEff<E>, Rest>> {
+    /// Discharge the hazard effect, stopping at the first failure.
+    #[inline]
+    pub fn handle_hazard(self) -> Eff<Hazard<A, E>, Rest> {
+        match self {
+            Self::Done(a) => Eff::Done(Success(a)),
+            Self::Request(Union::Head(HazardEff::Throw(e), _)) => Eff::Done(Failure(e)),
+            Self::Request(Union::Tail(u)) => {
+                Eff::Request(Rest::map_union(u, |m: Self| m.handle_hazard()))
+            }
+        }
+    }
+}
+
+impl<A: Clone> Eff<A, Nil> {
+    /// Return the value of a program with every effect handled.
+    #[inline(always)]
+    pub fn run(self) -> A {
+        match self {
+            Self::Done(a) => a,
+            Self::Request(never) => match never.0 {},
+        }
+    }
+}
+
+impl<A: Clone + 'static, E: Effect> Eff<A, Cons<E, Nil>> {
+    /// Discharge the last effect by answering each of its requests in some monad (e.g. `Io`), running them all in order.
+    #[inline]
+    pub fn run_m<M: MonadKind, H: Fn(E) -> M::Of<E::Answer> + Clone + 'static>(
+        self,
+        handler: H,
+    ) -> M::Of<A> {
+        match self {
+            Self::Done(a) => M::consume_in(a),
+            Self::Request(Union::Head(e, k)) => {
+                M::bind_in(handler(e), move |answer| k(answer).run_m::<M, H>(handler))
+            }
+            Self::Request(Union::Tail(never)) => match never.0 {},
+        }
+    }
+}
+
+impl<A: Clone, R: Row> Clone for Eff<A, R> {
+    #[inline]
+    fn clone(&self) -> Self {
+        #![allow(clippy::pattern_type_mismatch)]
+        match self {
+            Self::Done(a) => Self::Done(a.clone()),
+            Self::Request(u) => Self::Request(u.clone()),
+        }
+    }
+}
+
+impl<A: Clone, R: Row> core::fmt::Debug for Eff<A, R> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Self::Done(_) => f.write_str("Done(..)"),
+            Self::Request(_) => f.write_str("Request(..)"),
+        }
+    }
+}
+
+impl<E: Effect, Rest: Row, K> Clone for Union<E, Rest, K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        #![allow(clippy::pattern_type_mismatch)]
+        match self {
+            Self::Head(e, k) => Self::Head(e.clone(), Rc::clone(k)),
+            Self::Tail(u) => Self::Tail(u.clone()),
+        }
+    }
+}
+
+impl<K> Clone for Never<K> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        match self.0 {}
+    }
+}
+
+impl<A: quickcheck::Arbitrary, E: Effect + quickcheck::Arbitrary, Rest: Row> quickcheck::Arbitrary
+    for Eff<A, Cons<E, Rest>>
+where
+    E::Answer: core::hash::Hash,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // A handful of requests, with the value depending on the answer to the last one.
+        let mut requests = Vec::<E>::arbitrary(g);
+        requests.truncate(8);
+        let even = A::arbitrary(g);
+        let odd = A::arbitrary(g);
+        let mut program = match requests.pop() {
+            None => Self::Done(even),
+            Some(e) => Eff::<E::Answer, _>::send(e)
+                .bind(move |answer| Self::Done(if hash(answer) & 1 == 0 { even } else { odd })),
+        };
+        for e in requests.into_iter().rev() {
+            let rest = program;
+            program = Eff::<E::Answer, _>::send(e).bind(move |_| rest);
+        }
+        program
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    // Right-nested, as a program written as a loop would be: every step's continuation is fresh.
+    fn count<R, I, J>(n: u32) -> Eff<(), R>
+    where
+        R: Row + Member<StateEff<u32>, I> + Member<ReaderEff<u32>, J>,
+    {
+        if n == 0 {
+            return consume(());
+        }
+        Eff::ask()
+            >> move |step: u32| {
+                Eff::get()
+                    >> move |total: u32| Eff::put(total + step) >> move |()| count::<R, I, J>(n - 1)
+            }
+    }
+
+    #[test]
+    fn long_programs_run_in_constant_stack() {
+        const STEPS: u32 = 100_000;
+        // State handled first, with the reader's requests forwarded past it:
+        let state_first = count::<effects![StateEff<u32>, ReaderEff<u32>], _, _>(STEPS);
+        assert_eq!(
+            state_first.handle_state(0).handle_reader(2).run(),
+            ((), 2 * STEPS)
+        );
+        // And the other way around, through `interpret`:
+        let reader_first = count::<effects![ReaderEff<u32>, StateEff<u32>], _, _>(STEPS);
+        assert_eq!(
+            reader_first.handle_reader(2).handle_state(0).run(),
+            ((), 2 * STEPS)
+        );
+    }
+}
@@ -44,17 +44,17 @@ where
     }
     /// Folds a collection of lazy Alternatives. Starts with `empty` and combines with `either`.
     #[inline(always)]
-    fn asum<AA: Alternative<A>, A: Clone>(self) -> AA
+    fn asum<AA: Alternative<A>, A: Clone + 'static>(self) -> AA
     where
-        <Self as Fold>::Item: FnOnce() -> AA,
+        <Self as Fold>::Item: FnOnce() -> AA + 'static,
     {
         self.foldr(move |f, acc| acc.either(f), empty())
     }
     /// Folds a collection of Alternatives. Starts with `empty` and combines with `either`.
     #[inline(always)]
-    fn eager_asum<A: Clone>(self) -> <Self as Fold>::Item
+    fn eager_asum<A: Clone + 'static>(self) -> <Self as Fold>::Item
     where
-        <Self as Fold>::Item: Alternative<A>,
+        <Self as Fold>::Item: Alternative<A> + 'static,
     {
         self.foldr(move |f, acc| acc.either(|| f), empty())
     }
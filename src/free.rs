@@ -0,0 +1,202 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Free` monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::boxed::Box;
+
+/// A program made of instructions in any functor `T` (written as e.g. `Vec<()>` for `Vec<_>`; see `FunctorKind`), describing what to do without doing any of it.
+///
+/// Describe a program once with `lift_f` and `>>`, then run it however you like:
+/// `fold_free` interprets each instruction into any monad (e.g. `Io` for real, `State` for a simulation, or `Writer` for a log),
+/// and `run` steps through instructions one at a time with a plain function.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// use std::rc::Rc;
+///
+/// // Instructions for a tiny chat language, each holding whatever comes next:
+/// #[derive(Clone)]
+/// enum Chat<Next> {
+///     Say(String, Next),
+///     Ask(Rc<dyn Fn(String) -> Next>),
+/// }
+/// functor! {
+///     kind Chat<A>:
+///
+///     fn fmap(self, f) {
+///         match self {
+///             Chat::Say(s, next) => Chat::Say(s, f(next)),
+///             Chat::Ask(k) => Chat::Ask(Rc::new(move |line| f.clone()(k(line)))),
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// # #[cfg(feature = "std")]
+/// # {
+/// fn say(s: &str) -> Free<(), Chat<()>> {
+///     lift_f(Chat::Say(s.to_owned(), ()))
+/// }
+/// fn ask() -> Free<String, Chat<()>> {
+///     lift_f(Chat::Ask(Rc::new(|line| line)))
+/// }
+///
+/// // Describe the program once...
+/// let greet = say("Name?") >> |()| ask() >> |name| say(&format!("Hi, {name}!"));
+///
+/// // ...run it against a script...
+/// let mut said = vec![];
+/// greet.clone().run(|instruction| match instruction {
+///     Chat::Say(s, next) => {
+///         said.push(s);
+///         next
+///     }
+///     Chat::Ask(k) => k("Ferris".to_owned()),
+/// });
+/// assert_eq!(said, ["Name?", "Hi, Ferris!"]);
+///
+/// // ...or interpret it into any monad, e.g. a log of everything it says:
+/// #[derive(Clone)]
+/// struct Log;
+/// impl Interpreter<Chat<()>, Writer<(), List<String>>> for Log {
+///     fn interpret<X: Clone + 'static>(&self, instruction: Chat<X>) -> Writer<X, List<String>> {
+///         match instruction {
+///             Chat::Say(s, next) => Writer::new(next, list![s]),
+///             Chat::Ask(k) => consume(k("Ferris".to_owned())),
+///         }
+///     }
+/// }
+/// assert_eq!(
+///     greet.fold_free(Log).run_writer(),
+///     ((), list!["Name?".to_owned(), "Hi, Ferris!".to_owned()]),
+/// );
+/// # }
+/// # }
+/// ```
+pub enum Free<A: Clone, T: FunctorKind> {
+    /// A finished program: no more instructions, just a value.
+    Pure(A),
+    /// An instruction holding the rest of the program.
+    Roll(Box<T::Of<Self>>),
+}
+pub use Free::{Pure, Roll};
+
+monad! {
+    Free<A, T: FunctorKind + 'static>:
+
+    fn consume(a) {
+        Pure(a)
+    }
+
+    fn bind(self, f) {
+        match self {
+            Pure(a) => f(a),
+            Roll(fx) => Roll(Box::new(T::fmap_in(*fx, move |m: Free<A, T>| m.bind(f)))),
+        }
+    }
+
+    fn observe(m: Free<u64, Writer<(), SumU8>>) {
+        m.fold_free(Retract).run_writer()
+    }
+}
+
+/// Translation from instructions in a functor `T` to actions in a monad `M`, the same way for whatever comes next (Haskell's `forall x. f x -> m x`).
+pub trait Interpreter<T: FunctorKind, M: MonadKind> {
+    /// Turn one instruction into an action.
+    fn interpret<X: Clone + 'static>(&self, fx: T::Of<X>) -> M::Of<X>;
+}
+
+/// A program with a single instruction, returning whatever that instruction holds.
+#[inline(always)]
+pub fn lift_f<A: Clone + 'static, T: FunctorKind + 'static>(fa: T::Of<A>) -> Free<A, T> {
+    Roll(Box::new(T::fmap_in(fa, Pure)))
+}
+
+impl<A: Clone + 'static, T: FunctorKind + 'static> Free<A, T> {
+    /// Interpret every instruction into some monad and run them all in order.
+    #[inline]
+    pub fn fold_free<M: MonadKind, I: Interpreter<T, M> + Clone + 'static>(
+        self,
+        interpreter: I,
+    ) -> M::Of<A> {
+        match self {
+            Pure(a) => M::consume_in(a),
+            Roll(fx) => M::bind_in(interpreter.interpret(*fx), move |next: Self| {
+                next.fold_free(interpreter)
+            }),
+        }
+    }
+    /// Step through every instruction in order, each time handing `step` the next instruction and getting back the rest of the program.
+    /// Runs in constant stack space, however long the program.
+    #[inline]
+    pub fn run<G: FnMut(T::Of<Self>) -> Self>(self, mut step: G) -> A {
+        let mut program = self;
+        loop {
+            match program {
+                Pure(a) => return a,
+                Roll(fx) => program = step(*fx),
+            }
+        }
+    }
+}
+
+impl<A: Clone, T: FunctorKind> Clone for Free<A, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        #![allow(clippy::pattern_type_mismatch)]
+        match self {
+            Pure(a) => Pure(a.clone()),
+            Roll(fx) => Roll(fx.clone()),
+        }
+    }
+}
+
+impl<A: Clone, T: FunctorKind> core::fmt::Debug for Free<A, T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Pure(_) => f.write_str("Pure(..)"),
+            Roll(_) => f.write_str("Roll(..)"),
+        }
+    }
+}
+
+impl<A: quickcheck::Arbitrary, T: FunctorKind + 'static> quickcheck::Arbitrary for Free<A, T>
+where
+    T::Of<A>: quickcheck::Arbitrary,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // A handful of instructions, with the value coming from the last one.
+        let mut instructions = alloc::vec::Vec::<T::Of<A>>::arbitrary(g);
+        instructions.truncate(8);
+        let mut program = instructions
+            .pop()
+            .map_or_else(|| Pure(A::arbitrary(g)), lift_f);
+        for fa in instructions.into_iter().rev() {
+            let rest = program;
+            program = lift_f(fa).bind(move |_| rest);
+        }
+        program
+    }
+}
+
+/// Interpret `Writer` instructions as themselves, for observing programs in tests.
+#[cfg(test)]
+#[derive(Clone)]
+struct Retract;
+
+#[cfg(test)]
+impl Interpreter<Writer<(), SumU8>, Writer<(), SumU8>> for Retract {
+    #[inline(always)]
+    fn interpret<X: Clone + 'static>(&self, fx: Writer<X, SumU8>) -> Writer<X, SumU8> {
+        fx
+    }
+}
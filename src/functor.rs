@@ -7,6 +7,7 @@
 //! Functors.
 
 /// Container over which we can map a function.
+/// Functions and values passing through are `'static` so that lazy functors (e.g. `State`) can hold onto them.
 /// ```rust
 /// # #[cfg(feature = "std")] {
 /// use rsmonad::prelude::*;
@@ -18,14 +19,38 @@ pub trait Functor<A: Clone> {
     /// Fucking pain in the ass redundancy. This has to be in this trait to avoid potential spooky action at a distance e.g. by redefining a separate Hkt later.
     type Functor<B: Clone>: Functor<B, Functor<A> = Self>;
     /// Map a function over this functor.
-    fn fmap<B: Clone, F: FnOnce(A) -> B + Clone>(self, f: F) -> Self::Functor<B>;
+    fn fmap<B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static>(
+        self,
+        f: F,
+    ) -> Self::Functor<B>
+    where
+        A: 'static;
 }
 
 /// Map a function over a container.
 #[inline(always)]
-pub fn fmap<A: Clone, B: Clone, FA: Functor<A>, F: FnOnce(A) -> B + Clone>(
+pub fn fmap<
+    A: Clone + 'static,
+    B: Clone + 'static,
+    FA: Functor<A>,
+    F: FnOnce(A) -> B + Clone + 'static,
+>(
     f: F,
     fa: FA,
 ) -> FA::Functor<B> {
     fa.fmap(f)
 }
+
+/// A functor's type constructor on its own, without whatever it holds (the `f` in Haskell's `f a`).
+///
+/// Implemented for every functor at every type, so e.g. `Vec<()>` stands in for `Vec<_>`; see `MonadKind` for why `Functor::Functor<B>` can't do this by itself.
+/// `monad!` implements it for you, and so do `functor! { kind ... }` and `applicative! { kind ... }`, e.g. for the instructions of a `Free` program.
+pub trait FunctorKind {
+    /// This functor holding an `A`.
+    type Of<A: Clone>: Clone;
+    /// `fmap` in this functor.
+    fn fmap_in<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static>(
+        fa: Self::Of<A>,
+        f: F,
+    ) -> Self::Of<B>;
+}
@@ -6,7 +6,11 @@
 
 //! Crazy test cases that will seem like magic.
 
-#![allow(clippy::arithmetic_side_effects, clippy::panic)]
+#![allow(
+    clippy::arithmetic_side_effects,
+    clippy::missing_trait_methods,
+    clippy::panic
+)]
 
 use crate::prelude::*;
 
@@ -75,9 +79,9 @@ fn pythagoraean_triples() {
         list![1..20]
             >> |x| {
                 list![{ x }..20]
-                    >> |y| {
+                    >> move |y| {
                         list![{ y }..20]
-                            >> |z| guard::<List<()>>(x * x + y * y == z * z) >> |_| list![(x, y, z)]
+                            >> move |z| guard::<List<()>>(x * x + y * y == z * z) >> move |_| list![(x, y, z)]
                     }
             },
         list![
@@ -90,3 +94,257 @@ fn pythagoraean_triples() {
         ]
     );
 }
+
+#[test]
+fn stateful_fibonacci() {
+    fn step((): ()) -> State<(), (u64, u64)> {
+        State::modify(|(a, b)| (b, a + b))
+    }
+    let ten_steps = (0..10).fold(consume::<State<(), _>, _>(()), |acc, _| acc >> step);
+    assert_eq!(ten_steps.clone().exec_state((0, 1)), (55, 89));
+    assert_eq!(
+        (ten_steps >> |()| State::gets(|&(a, _)| a)).eval_state((0, 1)),
+        55
+    );
+}
+
+#[test]
+fn writer_log() {
+    fn step(x: u8) -> Writer<u8, List<String>> {
+        Writer::tell(list![format!("saw {x}")]) >> move |()| consume(x + 1)
+    }
+    let ((x, heard), log) = (step(1) >> step)
+        .listen()
+        .censor(|so_far| so_far + list!["done".to_owned()])
+        .run_writer();
+    assert_eq!(x, 3);
+    assert_eq!(heard, list!["saw 1".to_owned(), "saw 2".to_owned()]);
+    assert_eq!(
+        log,
+        list!["saw 1".to_owned(), "saw 2".to_owned(), "done".to_owned()]
+    );
+    assert_eq!(
+        Writer::new((x, |so_far: List<String>| so_far % |s| s.len().to_string()), log)
+            .pass()
+            .run_writer(),
+        (3, list!["5".to_owned(), "5".to_owned(), "4".to_owned()])
+    );
+}
+
+#[test]
+fn cont_early_exit() {
+    fn safe_div(n: u8, d: u8) -> Cont<u8, u8> {
+        Cont::call_cc(move |exit: Escape<u8, (), u8>| {
+            // Everything after `exit` is skipped, so we never actually divide by zero.
+            (if d == 0 { exit(u8::MAX) } else { consume(()) })
+                >> move |()| consume(n.wrapping_div(d))
+        })
+    }
+    assert_eq!(safe_div(12, 4).eval_cont(), 3);
+    assert_eq!(safe_div(12, 0).eval_cont(), u8::MAX);
+    // ...but only up to the end of `call_cc`: the rest of the outer chain still runs.
+    assert_eq!((safe_div(12, 0) >> |x| consume(x - 5)).eval_cont(), 250);
+}
+
+#[test]
+fn validation_collects_every_error() {
+    fn nonempty(field: &'static str, s: &'static str) -> Validation<&'static str, List<String>> {
+        if s.is_empty() {
+            Invalid(list![format!("{field} is empty")])
+        } else {
+            Valid(s)
+        }
+    }
+    let form = |name, email| {
+        nonempty("email", email) * (nonempty("name", name) % |n| move |e| format!("{n} <{e}>"))
+    };
+    assert_eq!(form("Will", "w@s.com"), Valid("Will <w@s.com>".to_owned()));
+    assert_eq!(
+        form("", ""),
+        Invalid(list!["email is empty".to_owned(), "name is empty".to_owned()])
+    );
+    // Converting keeps the combined failure, and from there on `>>` short-circuits as usual.
+    let hazard: Hazard<String, List<String>> = form("", "").into();
+    assert_eq!(Validation::from(hazard.clone()), form("", ""));
+    assert_eq!(
+        hazard >> |s| Success(s.len()),
+        Failure(list!["email is empty".to_owned(), "name is empty".to_owned()])
+    );
+}
+
+#[test]
+fn stream_laziness() {
+    // `|` doesn't call the right-hand side until the left runs out, so this never-ending recursion is fine.
+    fn from(n: u64) -> Stream<u64> {
+        Stream::cons(n, empty()) | move || from(n + 1)
+    }
+    assert_eq!(List::from(from(0).take(5)), list![0, 1, 2, 3, 4]);
+    assert_eq!(
+        List::from(Stream::iterate(1_u64, |x| x * 2).take(5)),
+        list![1, 2, 4, 8, 16]
+    );
+    assert_eq!(List::from(Stream::repeat('a').take(3)), list!['a', 'a', 'a']);
+    assert_eq!(
+        List::from(stream![1, 2].cycle().take(5)),
+        list![1, 2, 1, 2, 1]
+    );
+    assert!(Stream::<u8>::cycle(empty()).is_empty());
+    assert_eq!(stream![1_u16, 2, 3].foldl(|acc, x| acc * 10 + x, 0), 321);
+
+    // Each element is computed at most once, however many times we look at it.
+    let calls = alloc::rc::Rc::new(core::cell::Cell::new(0_u8));
+    let counter = alloc::rc::Rc::clone(&calls);
+    let doubled = stream![1_u8..] >> move |x| {
+        counter.set(counter.get() + 1);
+        consume(x * 2)
+    };
+    assert_eq!(List::from(doubled.clone().take(3)), list![2, 4, 6]);
+    assert_eq!(List::from(doubled.take(3)), list![2, 4, 6]);
+    assert_eq!(calls.get(), 3);
+
+    // Long, fully forced streams drop without recursing once per element.
+    let long = stream![0_u32..].take(1_000_000);
+    assert_eq!(long.clone().into_iter().count(), 1_000_000);
+    drop(long);
+}
+
+/// Instructions for a tiny counter language, each holding whatever comes next (see `free_program_three_ways`).
+#[derive(Clone)]
+enum Counter<Next> {
+    /// Add one to the count.
+    Incr(Next),
+    /// Look at the count.
+    Read(alloc::rc::Rc<dyn Fn(u8) -> Next>),
+}
+
+functor! {
+    kind Counter<A>:
+
+    fn fmap(self, f) {
+        match self {
+            Counter::Incr(next) => Counter::Incr(f(next)),
+            Counter::Read(k) => Counter::Read(alloc::rc::Rc::new(move |n| f.clone()(k(n)))),
+        }
+    }
+
+    fn observe(m: Counter<u64>) {
+        match m {
+            Counter::Incr(next) => (false, next),
+            Counter::Read(k) => (true, k(7)),
+        }
+    }
+}
+
+impl<A> core::fmt::Debug for Counter<A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Counter(<instruction>)")
+    }
+}
+
+impl<A: quickcheck::Arbitrary> quickcheck::Arbitrary for Counter<A> {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let next = A::arbitrary(g);
+        if bool::arbitrary(g) {
+            Self::Incr(next)
+        } else {
+            Self::Read(alloc::rc::Rc::new(move |_| next.clone()))
+        }
+    }
+}
+
+#[test]
+fn free_program_three_ways() {
+    use alloc::rc::Rc;
+
+    type Program<A> = Free<A, Counter<()>>;
+
+    // A pure simulation, starting from whatever count we like:
+    #[derive(Clone)]
+    struct Simulate;
+    impl Interpreter<Counter<()>, State<(), u8>> for Simulate {
+        fn interpret<X: Clone + 'static>(&self, fx: Counter<X>) -> State<X, u8> {
+            match fx {
+                Counter::Incr(next) => State::new(move |n| (next.clone(), n + 1)),
+                Counter::Read(k) => State::gets(move |&n| k(n)),
+            }
+        }
+    }
+
+    // A log of what happened, for tests, where every read sees zero:
+    #[derive(Clone)]
+    struct Log;
+    impl Interpreter<Counter<()>, Writer<(), List<&'static str>>> for Log {
+        fn interpret<X: Clone + 'static>(&self, fx: Counter<X>) -> Writer<X, List<&'static str>> {
+            match fx {
+                Counter::Incr(next) => Writer::new(next, list!["incr"]),
+                Counter::Read(k) => Writer::new(k(0), list!["read"]),
+            }
+        }
+    }
+
+    // And for real, printing as we go (here to a scripted console, to check what gets printed):
+    #[derive(Clone)]
+    struct Print;
+    impl Interpreter<Counter<()>, IoResult<()>> for Print {
+        fn interpret<X: Clone + 'static>(&self, fx: Counter<X>) -> IoResult<X> {
+            match fx {
+                Counter::Incr(next) => put_line("incr") >> move |()| consume(next),
+                Counter::Read(k) => put_line("read") >> move |()| consume(k(7)),
+            }
+        }
+    }
+
+    // Describe the program once...
+    let incr: Program<()> = lift_f(Counter::Incr(()));
+    let read: Program<u8> = lift_f(Counter::Read(Rc::new(|n| n)));
+    let program = incr.clone() >> move |()| incr >> move |()| read;
+
+    // ...and run it three different ways.
+    assert_eq!(program.clone().fold_free(Simulate).run_state(40), (42, 42));
+    assert_eq!(
+        program.clone().fold_free(Log).run_writer(),
+        (0, list!["incr", "incr", "read"])
+    );
+    let (outcome, console) = program.fold_free(Print).run_scripted(Vec::<String>::new());
+    assert_eq!(outcome, Success(7));
+    assert_eq!(console.stdout(), list!["incr", "incr", "read"].fmap(String::from));
+}
+
+#[test]
+fn eff_with_a_homemade_effect() {
+    // Effects are just plain data describing a request:
+    #[derive(Clone)]
+    enum Console {
+        Say(&'static str),
+        Listen,
+    }
+    impl Effect for Console {
+        type Answer = &'static str;
+    }
+    type Program<A> = Eff<A, effects![StateEff<u8>, Console]>;
+
+    // Greet someone, counting how many times we've spoken:
+    fn say(line: &'static str) -> Program<()> {
+        Eff::send(Console::Say(line)) >> |_| Eff::get() >> |n: u8| Eff::put(n + 1)
+    }
+    let program: Program<&'static str> =
+        say("name?") >> |()| Eff::send(Console::Listen) >> |name| say("hi!") % move |()| name;
+
+    // Handle the counter, then finish by talking to a script...
+    let scripted = program.clone().handle_state(0).interpret(|request| {
+        consume(match request {
+            Console::Say(line) => line,
+            Console::Listen => "Ferris",
+        })
+    });
+    assert_eq!(scripted.run(), ("Ferris", 2));
+
+    // ...or to the terminal (scripted again, so we can see what it says).
+    let real = program.handle_state(0).run_m::<IoResult<()>, _>(|request| match request {
+        Console::Say(line) => put_line(line) >> move |()| consume(line),
+        Console::Listen => consume("Ferris"),
+    });
+    let (outcome, console) = real.run_scripted(Vec::<String>::new());
+    assert_eq!(outcome, Success(("Ferris", 2)));
+    assert_eq!(console.stdout(), list!["name?", "hi!"].fmap(String::from));
+}
@@ -48,7 +48,7 @@ pub enum Hazard<A: Clone, E: Clone = DefaultErr> {
 pub use Hazard::{Failure, Success};
 
 monad! {
-    Hazard<A, E: Clone>:
+    Hazard<A, E: Clone + 'static>:
 
     fn consume(b) {
         Success(b)
@@ -62,6 +62,23 @@ monad! {
     }
 }
 
+impl<X: Clone, E: Clone + 'static> MonadError<E> for Hazard<X, E> {
+    #[inline(always)]
+    fn throw<A: Clone + 'static>(e: E) -> Hazard<A, E> {
+        Failure(e)
+    }
+    #[inline(always)]
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> Hazard<A, E> + Clone + 'static>(
+        m: Hazard<A, E>,
+        handler: F,
+    ) -> Hazard<A, E> {
+        match m {
+            Success(a) => Success(a),
+            Failure(e) => handler(e),
+        }
+    }
+}
+
 /// Convenience (D.R.Y.).
 #[cfg(feature = "nightly")]
 type Residual<E> = Hazard<core::convert::Infallible, E>;
@@ -72,7 +89,7 @@ impl<A: Clone, E: Clone> core::ops::Try for Hazard<A, E> {
     type Residual = Residual<E>;
     #[inline]
     fn from_output(a: A) -> Self {
-        consume(a)
+        Success(a)
     }
     #[inline]
     fn branch(self) -> core::ops::ControlFlow<Residual<E>, A> {
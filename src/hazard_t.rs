@@ -0,0 +1,259 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `HazardT` monad transformer.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+
+/// `Hazard` on top of any other monad `M` (written as e.g. `Vec<()>` for `Vec<_>`; see `MonadKind`).
+///
+/// Under the hood, just an `M` holding a `Hazard<A, E>`: `>>` runs `M`'s effects until the first `Failure`, then skips everything after it.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// // Count steps in a `State`, but give up on odd numbers:
+/// fn halve(x: u8) -> HazardT<u8, State<(), u8>, String> {
+///     let counted: HazardT<(), State<(), u8>, String> = lift(State::modify(|n: u8| n + 1));
+///     counted >> move |()| {
+///         if x % 2 == 0 {
+///             consume(x / 2)
+///         } else {
+///             HazardT::throw(format!("{x} is odd"))
+///         }
+///     }
+/// }
+/// assert_eq!(
+///     (halve(12) >> halve >> halve >> halve).run_hazard_t().run_state(0),
+///     (Failure("3 is odd".to_owned()), 3),
+/// );
+/// # }
+/// ```
+pub struct HazardT<A: Clone, M: MonadKind, E: Clone>(M::Of<Hazard<A, E>>);
+
+monad! {
+    HazardT<A, M: MonadKind + 'static, E: Clone + 'static>:
+
+    fn consume(a) {
+        Self(M::consume_in(Success(a)))
+    }
+
+    fn bind(self, f) {
+        HazardT(M::bind_in(self.0, move |ha| match ha {
+            Success(a) => f(a).0,
+            Failure(e) => M::consume_in(Failure(e)),
+        }))
+    }
+
+    fn observe(m: HazardT<u64, Option<()>, u64>) {
+        m.run_hazard_t()
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind, E: Clone + 'static> MonadTrans<A, M> for HazardT<A, M, E> {
+    #[inline(always)]
+    fn lift(m: M::Of<A>) -> Self {
+        Self(M::bind_in(m, |a| M::consume_in(Success(a))))
+    }
+}
+
+impl<A: Clone, M: MonadKind, E: Clone> HazardT<A, M, E> {
+    /// Wrap a `Hazard` inside the underlying monad.
+    #[inline(always)]
+    pub const fn new(m: M::Of<Hazard<A, E>>) -> Self {
+        Self(m)
+    }
+    /// Unwrap the underlying monad.
+    #[inline(always)]
+    pub fn run_hazard_t(self) -> M::Of<Hazard<A, E>> {
+        self.0
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind, E: Clone + 'static> HazardT<A, M, E> {
+    /// Fail, skipping every effect after this one.
+    #[inline(always)]
+    pub fn throw(e: E) -> Self {
+        Self(M::consume_in(Failure(e)))
+    }
+    /// Recover from a failure by running another computation with its reason.
+    #[inline(always)]
+    #[must_use]
+    pub fn catch<F: FnOnce(E) -> Self + Clone + 'static>(self, handler: F) -> Self {
+        Self(M::bind_in(self.0, move |ha| match ha {
+            Success(a) => M::consume_in(Success(a)),
+            Failure(e) => handler(e).0,
+        }))
+    }
+}
+
+impl<X: Clone, S: Clone + 'static, M: MonadState<S> + 'static, E: Clone + 'static> MonadState<S>
+    for HazardT<X, M, E>
+{
+    #[inline(always)]
+    fn get() -> HazardT<S, M, E> {
+        HazardT::lift(M::get())
+    }
+    #[inline(always)]
+    fn put(s: S) -> HazardT<(), M, E> {
+        HazardT::lift(M::put(s))
+    }
+}
+
+impl<X: Clone, R: Clone + 'static, M: MonadReader<R> + 'static, E: Clone + 'static> MonadReader<R>
+    for HazardT<X, M, E>
+{
+    #[inline(always)]
+    fn ask() -> HazardT<R, M, E> {
+        HazardT::lift(M::ask())
+    }
+    #[inline(always)]
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: HazardT<A, M, E>,
+    ) -> HazardT<A, M, E> {
+        HazardT(M::local(f, m.0))
+    }
+}
+
+impl<X: Clone, W: Monoid + Clone + 'static, M: MonadWriter<W> + 'static, E: Clone + 'static>
+    MonadWriter<W> for HazardT<X, M, E>
+{
+    #[inline(always)]
+    fn tell(w: W) -> HazardT<(), M, E> {
+        HazardT::lift(M::tell(w))
+    }
+    #[inline(always)]
+    fn listen<A: Clone + 'static>(m: HazardT<A, M, E>) -> HazardT<(A, W), M, E> {
+        HazardT(M::bind_in(M::listen(m.0), |(ha, w)| {
+            M::consume_in(match ha {
+                Success(a) => Success((a, w)),
+                Failure(e) => Failure(e),
+            })
+        }))
+    }
+}
+
+impl<X: Clone, M: MonadKind + 'static, E: Clone + 'static> MonadError<E> for HazardT<X, M, E> {
+    #[inline(always)]
+    fn throw<A: Clone + 'static>(e: E) -> HazardT<A, M, E> {
+        HazardT::throw(e)
+    }
+    #[inline(always)]
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> HazardT<A, M, E> + Clone + 'static>(
+        m: HazardT<A, M, E>,
+        handler: F,
+    ) -> HazardT<A, M, E> {
+        m.catch(handler)
+    }
+}
+
+impl<A: Clone, M: MonadKind, E: Clone> Clone for HazardT<A, M, E> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<A: Clone, M: MonadKind, E: Clone> core::fmt::Debug for HazardT<A, M, E>
+where
+    M::Of<Hazard<A, E>>: core::fmt::Debug,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("HazardT").field(&self.0).finish()
+    }
+}
+
+impl<A: Clone, M: MonadKind, E: Clone> PartialEq for HazardT<A, M, E>
+where
+    M::Of<Hazard<A, E>>: PartialEq,
+{
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static, E: Clone + 'static> quickcheck::Arbitrary
+    for HazardT<A, M, E>
+where
+    M::Of<Hazard<A, E>>: quickcheck::Arbitrary,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self(M::Of::<Hazard<A, E>>::arbitrary(g))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod over_std {
+    //! Laws again, this time over some more interesting base monads.
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    mod over_list {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: HazardT<u64, List<()>, u64>) {
+                m.run_hazard_t()
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: HazardT<u64, List<()>, u64>) {
+                m.run_hazard_t()
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: HazardT<u64, List<()>, u64>) {
+                m.run_hazard_t()
+            }
+        );
+    }
+
+    mod over_state {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: HazardT<u64, State<(), u64>, u64>, s: u64) {
+                m.run_hazard_t().run_state(s)
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: HazardT<u64, State<(), u64>, u64>, s: u64) {
+                m.run_hazard_t().run_state(s)
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: HazardT<u64, State<(), u64>, u64>, s: u64) {
+                m.run_hazard_t().run_state(s)
+            }
+        );
+    }
+
+    #[test]
+    fn failure_skips_later_effects() {
+        let push = |x: u8| -> HazardT<(), State<(), List<u8>>, u8> {
+            lift(State::modify(move |mut xs: List<u8>| {
+                xs.push(x);
+                xs
+            }))
+        };
+        let program = push(1) >> move |()| HazardT::throw(2) >> move |()| push(3);
+        assert_eq!(
+            program.clone().run_hazard_t().run_state(list![]),
+            (Failure(2), list![1])
+        );
+        let recovered = program.catch(push) >> move |()| push(4);
+        assert_eq!(
+            recovered.run_hazard_t().run_state(list![]),
+            (Success(()), list![1, 2, 4])
+        );
+    }
+}
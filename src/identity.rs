@@ -0,0 +1,47 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Identity` monad.
+
+#![allow(clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+
+/// The trivial monad: just a value, with `>>` as plain function application.
+/// Useful wherever generic code wants some `Monad` and you don't need any effects.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// fn successor(x: u8) -> Identity<u8> {
+///     Identity(x + 1)
+/// }
+/// assert_eq!(
+///     (Identity(3) >> successor >> successor).run_identity(),
+///     5,
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, QuickCheck)]
+pub struct Identity<A>(pub A);
+
+monad! {
+    Identity<A>:
+
+    fn consume(a) {
+        Self(a)
+    }
+
+    fn bind(self, f) {
+        f(self.0)
+    }
+}
+
+impl<A> Identity<A> {
+    /// Unwrap the value.
+    #[inline(always)]
+    pub fn run_identity(self) -> A {
+        self.0
+    }
+}
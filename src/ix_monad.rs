@@ -0,0 +1,66 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Trait definition.
+
+/// Monad whose computations move between type-level states: `M<I, J, A>` starts in state `I`, ends in state `J`, and returns an `A`.
+///
+/// `>>` only lines up when one computation ends in the state where the next one starts,
+/// so protocols (e.g. open, then write, then close) can turn out-of-order steps into compile errors (see `IxState`).
+///
+/// Original Haskell definition (from `indexed`, with `IxPointed` folded in):
+/// ```haskell
+/// class IxMonad m where
+/// ireturn :: a -> m i i a
+/// ibind   :: (a -> m j k b) -> m i j a -> m i k b
+/// ```
+pub trait IxMonad<I, J, A: Clone> {
+    /// Same kind of computation, between different states and with a different value.
+    type IxMonad<K, L, B: Clone>: IxMonad<K, L, B, IxMonad<I, J, A> = Self>;
+    /// Return a value without leaving the state we started in.
+    fn ireturn(a: A) -> Self::IxMonad<I, I, A>
+    where
+        I: 'static,
+        A: 'static;
+    /// Pick up wherever this computation left off with another computation depending on its value.
+    fn ibind<
+        K: 'static,
+        B: Clone + 'static,
+        F: FnOnce(A) -> Self::IxMonad<J, K, B> + Clone + 'static,
+    >(
+        self,
+        f: F,
+    ) -> Self::IxMonad<I, K, B>
+    where
+        I: 'static,
+        J: 'static,
+        A: 'static;
+}
+
+/// Return a value without leaving the state we started in.
+#[inline(always)]
+pub fn ireturn<M: IxMonad<I, I, A, IxMonad<I, I, A> = M>, I: 'static, A: Clone + 'static>(
+    a: A,
+) -> M {
+    M::ireturn(a)
+}
+
+/// Pick up wherever one computation left off with another computation depending on its value.
+#[inline(always)]
+pub fn ibind<
+    I: 'static,
+    J: 'static,
+    K: 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+    M: IxMonad<I, J, A>,
+    F: FnOnce(A) -> M::IxMonad<J, K, B> + Clone + 'static,
+>(
+    m: M,
+    f: F,
+) -> M::IxMonad<I, K, B> {
+    m.ibind(f)
+}
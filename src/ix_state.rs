@@ -0,0 +1,168 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `IxState` (indexed state) monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::rc::Rc;
+
+/// `State` where each step can change the type of the state: start with an `I`, end with a `J`, and return an `A`.
+/// Under the hood, just a function `I -> (A, J)`.
+///
+/// Since `>>` only accepts a next step that starts where the last one ended, the state's type can track where we are in a protocol.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// // A file that knows whether it's open, holding whatever's been written so far:
+/// struct Open(Vec<&'static str>);
+/// struct Closed(Vec<&'static str>);
+///
+/// fn open() -> IxState<Closed, Open, ()> {
+///     IxState::modify(|Closed(lines)| Open(lines))
+/// }
+/// fn write(line: &'static str) -> IxState<Open, Open, ()> {
+///     IxState::modify(move |Open(mut lines)| {
+///         lines.push(line);
+///         Open(lines)
+///     })
+/// }
+/// fn close() -> IxState<Open, Closed, ()> {
+///     IxState::modify(|Open(lines)| Closed(lines))
+/// }
+///
+/// let session = open() >> |()| write("hello") >> |()| write("world") >> |()| close();
+/// let Closed(lines) = session.exec_ix_state(Closed(vec![]));
+/// assert_eq!(lines, ["hello", "world"]);
+/// # }
+/// ```
+/// Writing after closing doesn't compile, since `write` needs an `Open` file and `close` leaves it `Closed`:
+#[cfg_attr(feature = "alloc", doc = "```rust,compile_fail")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,ignore")]
+/// # use rsmonad::prelude::*;
+/// # struct Open(Vec<&'static str>);
+/// # struct Closed(Vec<&'static str>);
+/// #
+/// # fn open() -> IxState<Closed, Open, ()> {
+/// #     IxState::modify(|Closed(lines)| Open(lines))
+/// # }
+/// # fn write(line: &'static str) -> IxState<Open, Open, ()> {
+/// #     IxState::modify(move |Open(mut lines)| {
+/// #         lines.push(line);
+/// #         Open(lines)
+/// #     })
+/// # }
+/// # fn close() -> IxState<Open, Closed, ()> {
+/// #     IxState::modify(|Open(lines)| Closed(lines))
+/// # }
+/// #
+/// let session = open() >> |()| write("hello") >> |()| close() >> |()| write("world");
+/// ```
+pub struct IxState<I, J, A>(Rc<dyn Fn(I) -> (A, J)>);
+
+ix_monad! {
+    IxState<I, J, A>:
+
+    fn ireturn(a) {
+        IxState::new(move |i| (a.clone(), i))
+    }
+
+    fn ibind(self, f) {
+        IxState::new(move |i| {
+            let (a, j) = (self.0)(i);
+            (f.clone()(a).0)(j)
+        })
+    }
+
+    fn observe(m: IxState<u64, u64, u64>, s: u64) {
+        m.run_ix_state(s)
+    }
+}
+
+impl<I: 'static, J: 'static, A> IxState<I, J, A> {
+    /// Wrap a function from the starting state to a value and the ending state.
+    #[inline(always)]
+    pub fn new<F: Fn(I) -> (A, J) + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run this computation from some starting state, returning both the final value and the ending state.
+    #[inline(always)]
+    pub fn run_ix_state(self, i: I) -> (A, J) {
+        (self.0)(i)
+    }
+    /// Run this computation from some starting state and keep only the final value.
+    #[inline(always)]
+    pub fn eval_ix_state(self, i: I) -> A {
+        self.run_ix_state(i).0
+    }
+    /// Run this computation from some starting state and keep only the ending state.
+    #[inline(always)]
+    pub fn exec_ix_state(self, i: I) -> J {
+        self.run_ix_state(i).1
+    }
+}
+
+impl<S: 'static, A> IxState<S, S, A> {
+    /// Read some function of the current state without changing it.
+    #[inline(always)]
+    pub fn gets<F: Fn(&S) -> A + 'static>(f: F) -> Self {
+        Self::new(move |s| (f(&s), s))
+    }
+}
+
+impl<S: Clone + 'static> IxState<S, S, S> {
+    /// Read the current state without changing it.
+    #[inline(always)]
+    #[must_use]
+    pub fn get() -> Self {
+        Self::new(|s: S| (s.clone(), s))
+    }
+}
+
+impl<I: 'static, J: 'static> IxState<I, J, ()> {
+    /// Replace the current state, possibly with one of a different type.
+    #[inline(always)]
+    pub fn put(j: J) -> Self
+    where
+        J: Clone,
+    {
+        Self::new(move |_| ((), j.clone()))
+    }
+    /// Replace the current state with some function of it, possibly of a different type.
+    #[inline(always)]
+    pub fn modify<F: Fn(I) -> J + 'static>(f: F) -> Self {
+        Self::new(move |i| ((), f(i)))
+    }
+}
+
+impl<I, J, A> Clone for IxState<I, J, A> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<I, J, A> core::fmt::Debug for IxState<I, J, A> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("IxState(<function>)")
+    }
+}
+
+impl<S: quickcheck::Arbitrary, A: quickcheck::Arbitrary> quickcheck::Arbitrary
+    for IxState<S, S, A>
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // Either leave the state alone or overwrite it, then return a constant.
+        let a = A::arbitrary(g);
+        let overwrite = Option::<S>::arbitrary(g);
+        Self::new(move |s| (a.clone(), overwrite.clone().unwrap_or(s)))
+    }
+}
@@ -31,6 +31,9 @@
     clippy::single_char_lifetime_names
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod prelude {
     //! In general, always import this with `use rsmonad::prelude::*;`.
 
@@ -43,15 +46,52 @@ pub mod prelude {
 
     pub use super::alternative::*;
     pub use super::applicative::*;
+    pub use super::capability::*;
     pub use super::fold::*;
     pub use super::functor::*;
+    pub use super::ix_monad::*;
     pub use super::monad::*;
+    pub use super::monad_trans::*;
     pub use super::monoid::*;
 
+    #[cfg(feature = "alloc")]
+    pub use super::codensity::*;
+    pub use super::constant::*;
+    #[cfg(feature = "alloc")]
+    pub use super::cont::*;
+    #[cfg(feature = "alloc")]
+    pub use super::eff::*;
+    #[cfg(feature = "alloc")]
+    pub use super::free::*;
     pub use super::hazard::*;
+    pub use super::hazard_t::*;
+    pub use super::identity::*;
+    #[cfg(feature = "alloc")]
+    pub use super::ix_state::*;
+    #[cfg(feature = "alloc")]
+    pub use super::list_t::*;
     pub use super::maybe::*;
+    pub use super::maybe_t::*;
+    #[cfg(feature = "alloc")]
+    pub use super::non_empty::*;
     pub use super::prod_u8::*;
+    #[cfg(feature = "alloc")]
+    pub use super::reader::*;
+    #[cfg(feature = "alloc")]
+    pub use super::reader_t::*;
+    #[cfg(feature = "alloc")]
+    pub use super::rws::*;
+    #[cfg(feature = "alloc")]
+    pub use super::state::*;
+    #[cfg(feature = "alloc")]
+    pub use super::state_t::*;
+    #[cfg(feature = "alloc")]
+    pub use super::stream::*;
     pub use super::sum_u8::*;
+    pub use super::these::*;
+    pub use super::validation::*;
+    pub use super::writer::*;
+    pub use super::writer_t::*;
 
     #[cfg(feature = "std")]
     pub use super::with_std::*;
@@ -63,15 +103,52 @@ mod macros;
 
 mod alternative;
 mod applicative;
+mod capability;
 mod fold;
 mod functor;
+mod ix_monad;
 mod monad;
+mod monad_trans;
 mod monoid;
 
+#[cfg(feature = "alloc")]
+mod codensity;
+mod constant;
+#[cfg(feature = "alloc")]
+mod cont;
+#[cfg(feature = "alloc")]
+mod eff;
+#[cfg(feature = "alloc")]
+mod free;
 mod hazard;
+mod hazard_t;
+mod identity;
+#[cfg(feature = "alloc")]
+mod ix_state;
+#[cfg(feature = "alloc")]
+mod list_t;
 mod maybe;
+mod maybe_t;
+#[cfg(feature = "alloc")]
+mod non_empty;
 mod prod_u8;
+#[cfg(feature = "alloc")]
+mod reader;
+#[cfg(feature = "alloc")]
+mod reader_t;
+#[cfg(feature = "alloc")]
+mod rws;
+#[cfg(feature = "alloc")]
+mod state;
+#[cfg(feature = "alloc")]
+mod state_t;
+#[cfg(feature = "alloc")]
+mod stream;
 mod sum_u8;
+mod these;
+mod validation;
+mod writer;
+mod writer_t;
 
 mod orphans;
 
@@ -0,0 +1,373 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `ListT` monad transformer.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::{just_alternative, prelude::*};
+use alloc::{rc::Rc, vec::Vec};
+use core::cell::{Cell, OnceCell};
+
+/// Lazy list whose every cell sits behind an effect in another monad `M` (written as e.g. `Vec<()>` for `Vec<_>`; see `MonadKind`).
+///
+/// Under the hood, just a thunk `() -> M<Maybe<(A, ListT<A, M>)>>`: nothing runs until someone asks for the next cell,
+/// so (unlike `M` wrapped around a whole `List`) producing and consuming elements interleave, and infinite lists are fine as long as you only ask for finitely many.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// type Logged = State<(), List<String>>;
+/// // Count upward forever, logging each number as we make it:
+/// fn naturals(from: u8) -> ListT<u8, Logged> {
+///     ListT::new(move || {
+///         State::modify(move |log| log + list![format!("made {from}")])
+///             >> move |()| consume(Just((from, naturals(from + 1))))
+///     })
+/// }
+/// let log = naturals(0)
+///     .take(2)
+///     .fold_m((), |(), n| State::modify(move |log| log + list![format!("used {n}")]))
+///     .exec_state(list![]);
+/// assert_eq!(log, list!["made 0", "used 0", "made 1", "used 1"].fmap(String::from));
+/// # }
+/// ```
+pub struct ListT<A: Clone, M: MonadKind>(Rc<dyn Fn() -> M::Of<Step<A, M>>>);
+
+/// One cell of a `ListT`: either the end of the list or an element and the rest of the list.
+pub type Step<A, M> = Maybe<(A, ListT<A, M>)>;
+
+monad! {
+    ListT<A, M: MonadKind + 'static>:
+
+    fn consume(a) {
+        Self::cons(a, empty())
+    }
+
+    fn bind(self, f) {
+        ListT::new(move || {
+            let f = f.clone();
+            M::bind_in(self.uncons(), move |step| match step {
+                Just((a, rest)) => f.clone()(a).append(rest.bind(f)).uncons(),
+                Nothing => M::consume_in(Nothing),
+            })
+        })
+    }
+
+    fn observe(m: ListT<u64, Option<()>>) {
+        m.run_list_t()
+    }
+}
+
+just_alternative! {
+    ListT<A, M: MonadKind + 'static>:
+
+    fn empty() {
+        Self::new(|| M::consume_in(Nothing))
+    }
+
+    fn either(self, make_other) {
+        let pending = Rc::new((Cell::new(Some(make_other)), OnceCell::new()));
+        self.append(ListT::new(move || {
+            pending.1.get_or_init(|| pending.0.take().map_or_else(empty, |f| f())).uncons()
+        }))
+    }
+
+    fn observe(m: ListT<u64, Option<()>>) {
+        m.run_list_t()
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static> MonadTrans<A, M> for ListT<A, M> {
+    #[inline(always)]
+    fn lift(m: M::Of<A>) -> Self {
+        Self::new(move || M::bind_in(m.clone(), |a| M::consume_in(Just((a, empty())))))
+    }
+}
+
+impl<A: Clone, M: MonadKind> ListT<A, M> {
+    /// Wrap an effect that, whenever it's run, produces the first cell of a list.
+    #[inline(always)]
+    pub fn new<F: Fn() -> M::Of<Step<A, M>> + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run the effect behind the first cell of this list.
+    #[inline(always)]
+    #[must_use]
+    pub fn uncons(&self) -> M::Of<Step<A, M>> {
+        (self.0)()
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static> ListT<A, M> {
+    /// Prepend an element without any effects of its own.
+    #[inline(always)]
+    #[must_use]
+    pub fn cons(a: A, tail: Self) -> Self {
+        Self::new(move || M::consume_in(Just((a.clone(), tail.clone()))))
+    }
+    /// Everything in this list followed by everything in another, running the other's effects only once this one runs out.
+    #[inline(always)]
+    #[must_use]
+    pub fn append(self, other: Self) -> Self {
+        Self::new(move || {
+            let next = other.clone();
+            M::bind_in(self.uncons(), move |step| match step {
+                Just((a, rest)) => M::consume_in(Just((a, rest.append(next)))),
+                Nothing => next.uncons(),
+            })
+        })
+    }
+    /// Keep at most the first `n` elements, never running the effects behind any later ones.
+    #[inline(always)]
+    #[must_use]
+    pub fn take(self, n: usize) -> Self {
+        Self::new(move || {
+            let Some(left) = n.checked_sub(1) else {
+                return M::consume_in(Nothing);
+            };
+            M::bind_in(self.uncons(), move |step| {
+                M::consume_in(match step {
+                    Just((a, rest)) => Just((a, rest.take(left))),
+                    Nothing => Nothing,
+                })
+            })
+        })
+    }
+    /// Fold over every element with an effectful function, running each element's effects just before that element is folded in.
+    ///
+    /// Each element nests another `bind` inside the last, so over a base monad that runs `bind` right away (e.g. `Option`),
+    /// the stack grows with the length of the list: a few thousand elements is about as far as a default thread's stack goes in a debug build.
+    #[inline]
+    pub fn fold_m<B: Clone + 'static, F: FnOnce(B, A) -> M::Of<B> + Clone + 'static>(
+        self,
+        b: B,
+        f: F,
+    ) -> M::Of<B> {
+        M::bind_in(self.uncons(), move |step| match step {
+            Just((a, rest)) => M::bind_in(f.clone()(b, a), move |acc| rest.fold_m(acc, f)),
+            Nothing => M::consume_in(b),
+        })
+    }
+    /// Run every effect in this list, collecting all its elements (with the same limit on length as `fold_m`).
+    #[inline(always)]
+    #[must_use]
+    pub fn run_list_t(self) -> M::Of<Vec<A>> {
+        self.fold_m(Vec::new(), |mut acc, a| {
+            acc.push(a);
+            M::consume_in(acc)
+        })
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static> FromIterator<A> for ListT<A, M> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        iter.into_iter()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .fold(empty(), |tail, a| Self::cons(a, tail))
+    }
+}
+
+impl<X: Clone, S: Clone + 'static, M: MonadState<S> + 'static> MonadState<S> for ListT<X, M> {
+    #[inline(always)]
+    fn get() -> ListT<S, M> {
+        ListT::lift(M::get())
+    }
+    #[inline(always)]
+    fn put(s: S) -> ListT<(), M> {
+        ListT::lift(M::put(s))
+    }
+}
+
+impl<X: Clone, R: Clone + 'static, M: MonadReader<R> + 'static> MonadReader<R> for ListT<X, M> {
+    #[inline(always)]
+    fn ask() -> ListT<R, M> {
+        ListT::lift(M::ask())
+    }
+    #[inline(always)]
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: ListT<A, M>,
+    ) -> ListT<A, M> {
+        // Every cell's effect, not just the first, has to see the modified environment.
+        ListT::new(move || {
+            let g = f.clone();
+            M::bind_in(M::local(f.clone(), m.uncons()), move |step| {
+                M::consume_in(match step {
+                    Just((a, rest)) => Just((a, Self::local(g, rest))),
+                    Nothing => Nothing,
+                })
+            })
+        })
+    }
+}
+
+impl<X: Clone, W: Monoid + Clone + 'static, M: MonadWriter<W> + 'static> MonadWriter<W>
+    for ListT<X, M>
+{
+    #[inline(always)]
+    fn tell(w: W) -> ListT<(), M> {
+        ListT::lift(M::tell(w))
+    }
+    /// Pair each element with what was logged while producing it (i.e. since the element before it).
+    #[inline(always)]
+    fn listen<A: Clone + 'static>(m: ListT<A, M>) -> ListT<(A, W), M> {
+        ListT::new(move || {
+            M::bind_in(M::listen(m.uncons()), |(step, w)| {
+                M::consume_in(match step {
+                    Just((a, rest)) => Just(((a, w), Self::listen(rest))),
+                    Nothing => Nothing,
+                })
+            })
+        })
+    }
+}
+
+impl<X: Clone, E: Clone + 'static, M: MonadError<E> + 'static> MonadError<E> for ListT<X, M> {
+    #[inline(always)]
+    fn throw<A: Clone + 'static>(e: E) -> ListT<A, M> {
+        ListT::lift(M::throw(e))
+    }
+    /// A failure in any cell gives up on the rest of the list and continues with the handler's list instead.
+    #[inline(always)]
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> ListT<A, M> + Clone + 'static>(
+        m: ListT<A, M>,
+        handler: F,
+    ) -> ListT<A, M> {
+        ListT::new(move || {
+            let (h, g) = (handler.clone(), handler.clone());
+            M::catch(
+                M::bind_in(m.uncons(), move |step| {
+                    M::consume_in(match step {
+                        Just((a, rest)) => Just((a, Self::catch(rest, g))),
+                        Nothing => Nothing,
+                    })
+                }),
+                move |e| h(e).uncons(),
+            )
+        })
+    }
+}
+
+impl<A: Clone, M: MonadKind> Clone for ListT<A, M> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A: Clone, M: MonadKind> core::fmt::Debug for ListT<A, M> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ListT(<function>)")
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static> quickcheck::Arbitrary for ListT<A, M>
+where
+    M::Of<A>: quickcheck::Arbitrary,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // One effect per element, and only a few of them, since `>>` runs a whole new list's worth of effects for every element.
+        let mut effects = Vec::<M::Of<A>>::arbitrary(g);
+        effects.truncate(8);
+        effects
+            .into_iter()
+            .rev()
+            .fold(empty(), |tail, m| Self::lift(m).append(tail))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod over_std {
+    //! Laws again, this time over some more interesting base monads.
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    mod over_io {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: ListT<u64, Io<()>>) {
+                m.run_list_t().run()
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: ListT<u64, Io<()>>) {
+                m.run_list_t().run()
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: ListT<u64, Io<()>>) {
+                m.run_list_t().run()
+            }
+        );
+    }
+
+    mod over_state {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: ListT<u64, State<(), u64>>, s: u64) {
+                m.run_list_t().run_state(s)
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: ListT<u64, State<(), u64>>, s: u64) {
+                m.run_list_t().run_state(s)
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: ListT<u64, State<(), u64>>, s: u64) {
+                m.run_list_t().run_state(s)
+            }
+        );
+        crate::test_alternative!(
+            fn observe(m: ListT<u64, State<(), u64>>, s: u64) {
+                m.run_list_t().run_state(s)
+            }
+        );
+    }
+
+    #[test]
+    fn take_skips_later_effects() {
+        // Every cell fails in the base monad, but we never ask for any of them.
+        let failing: ListT<u8, Option<()>> = ListT::new(|| None);
+        assert_eq!(failing.clone().take(0).run_list_t(), Some(vec![]));
+        assert_eq!(failing.take(1).run_list_t(), None);
+    }
+
+    #[test]
+    fn infinite_list() {
+        fn from(n: u8) -> ListT<u8, Option<()>> {
+            ListT::new(move || Some(Just((n, from(n.wrapping_add(1))))))
+        }
+        assert_eq!(
+            (from(0) >> |x| consume(x * 2)).take(4).run_list_t(),
+            Some(vec![0, 2, 4, 6])
+        );
+    }
+
+    #[test]
+    fn long_strict_fold() {
+        // Comfortably within the limit documented on `fold_m`, even on a test thread's stack:
+        let xs: ListT<usize, Option<()>> = (0..1_000).collect();
+        assert_eq!(xs.fold_m(0_usize, usize::checked_add), Some(499_500));
+    }
+
+    #[test]
+    fn infinite_alternative() {
+        // Each alternative refers to the next, so the fallback can only be built once we actually get to it.
+        fn naturals(n: u64) -> ListT<u64, Option<()>> {
+            ListT::consume(n) | move || naturals(n + 1)
+        }
+        assert_eq!(naturals(0).take(3).run_list_t(), Some(vec![0, 1, 2]));
+    }
+}
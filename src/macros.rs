@@ -9,6 +9,7 @@
 pub use paste::paste;
 
 /// Test the functor laws.
+/// With `fn observe(...) { ... }`, compares what `observe` returns instead of the values themselves (for types like `State` that can't implement `PartialEq`).
 #[macro_export]
 macro_rules! test_functor {
     ($name:ident<u64>) => {
@@ -23,10 +24,30 @@ macro_rules! test_functor {
             }
         }
     };
+    ($name:ident<u64> fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        $crate::test_functor!(fn observe($m: $ty $(, $x: $x_ty)?) $observe);
+    };
+    (fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        quickcheck::quickcheck! {
+            fn prop_functor_identity(fa: $ty $(, $x: $x_ty)?) -> bool {
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(fa.clone() $(, $x.clone())?) == observe(fa.fmap(core::convert::identity) $(, $x)?)
+            }
+            fn prop_functor_composition(fa: $ty $(, $x: $x_ty)?) -> bool {
+                use $crate::entropy::hash as g;
+                use $crate::entropy::reverse as h;
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(fa.clone().fmap(move |a| g(h(a))) $(, $x.clone())?) == observe(fa.fmap(h).fmap(g) $(, $x)?)
+            }
+        }
+    };
 }
 pub use test_functor;
 
 /// Implement `Functor` (and its superclasses automatically) after a definition.
+///
+/// Starting with `kind` (e.g. `functor! { kind Pointless<A>: ... }`) also implements `FunctorKind`, e.g. to use it as the instructions of a `Free` program;
+/// that needs the type to be `Clone` whenever what it holds is.
 /// ```rust
 /// use rsmonad::prelude::*;
 ///
@@ -47,7 +68,18 @@ pub use test_functor;
 /// ```
 #[macro_export]
 macro_rules! functor {
-    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:path $(, $g_bounds:path)*)?),+)?>: fn fmap($self:ident, $f:ident) $fmap:block) => {
+    (kind $name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn fmap($self:ident, $f:ident) $fmap:block $(fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block)?) => {
+        $crate::prelude::functor! {
+            $name<A $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?>:
+
+            fn fmap($self, $f) $fmap
+
+            $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?
+        }
+
+        $crate::functor_kind!($name<A $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?>);
+    };
+    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn fmap($self:ident, $f:ident) $fmap:block $(fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block)?) => {
         paste! {
             mod [<$name:snake _functor_impl>] {
                 #![allow(unused_imports, unused_mut)]
@@ -56,22 +88,43 @@ macro_rules! functor {
 
                 impl<A: Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> Functor<A> for $name<A $(, $($g_ty),+)?> {
                     type Functor<B: Clone> = $name<B $(, $($g_ty),+)?>;
-                    #[inline(always)] #[must_use] fn fmap<B: Clone, F: FnOnce(A) -> B + Clone>(mut $self, mut $f: F) -> $name<B $(, $($g_ty),+)?> $fmap
+                    #[inline(always)] #[must_use] fn fmap<B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static>(mut $self, mut $f: F) -> $name<B $(, $($g_ty),+)?> where A: 'static $fmap
                 }
 
-                impl<A: Clone, B: Clone, F: FnOnce(A) -> B + Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::Rem<F> for $name<A $(, $($g_ty),+)?> {
+                impl<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::Rem<F> for $name<A $(, $($g_ty),+)?> {
                     type Output = $name<B $(, $($g_ty),+)?>;
                     #[inline(always)] #[must_use] fn rem(mut self, mut f: F) -> $name<B $(, $($g_ty),+)?> { self.fmap(f) }
                 }
 
-                $crate::test_functor!($name<u64>);
+                $crate::test_functor!($name<u64> $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?);
             }
         }
     };
 }
 pub use functor;
 
+/// Implement `FunctorKind` for a type that already implements `Functor`; see `functor! { kind ... }`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! functor_kind {
+    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>) => {
+        paste! {
+            mod [<$name:snake _functor_kind_impl>] {
+                #![allow(unused_imports)]
+                use $crate::prelude::*;
+                use super::*;
+
+                impl<X: Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> FunctorKind for $name<X $(, $($g_ty),+)?> {
+                    type Of<A: Clone> = $name<A $(, $($g_ty),+)?>;
+                    #[inline(always)] fn fmap_in<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static>(fa: $name<A $(, $($g_ty),+)?>, f: F) -> $name<B $(, $($g_ty),+)?> { fa.fmap(f) }
+                }
+            }
+        }
+    };
+}
+
 /// Test the Applicative laws.
+/// With `fn observe(...) { ... }`, compares what `observe` returns instead of the values themselves (for types like `State` that can't implement `PartialEq`).
 #[macro_export]
 macro_rules! test_applicative {
     ($name:ident<u64>) => {
@@ -105,13 +158,49 @@ macro_rules! test_applicative {
             */
         }
     };
+    ($name:ident<u64> fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        $crate::test_applicative!(fn observe($m: $ty $(, $x: $x_ty)?) $observe);
+    };
+    (fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        quickcheck::quickcheck! {
+            fn prop_applicative_fmap(ab: $ty $(, $x: $x_ty)?) -> bool {
+                use $crate::entropy::hash as f;
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(ab.clone().fmap(f) $(, $x.clone())?) == observe(ab.tie(consume(f)) $(, $x)?)
+            }
+            fn prop_applicative_identity(ab: $ty $(, $x: $x_ty)?) -> bool {
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(ab.clone() $(, $x.clone())?) == observe(ab.tie(consume(core::convert::identity)) $(, $x)?)
+            }
+            fn prop_applicative_homomorphism(b: u64 $(, $x: $x_ty)?) -> bool {
+                use $crate::entropy::hash as f;
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(consume::<$ty, _>(f(b)) $(, $x.clone())?) == observe(consume::<$ty, _>(b).tie(consume(f)) $(, $x)?)
+            }
+        }
+    };
 }
 pub use test_applicative;
 
 /// Implement `Applicative` (and its superclasses automatically) after a definition.
+///
+/// Like `functor!`, starting with `kind` also implements `FunctorKind`.
 #[macro_export]
 macro_rules! applicative {
-    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn consume($a:ident) $consume:block fn tie($self:ident, $af:ident) $tie:block) => {
+    (kind $name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn consume($a:ident) $consume:block fn tie($self:ident, $af:ident) $tie:block $(fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block)?) => {
+        $crate::prelude::applicative! {
+            $name<A $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?>:
+
+            fn consume($a) $consume
+
+            fn tie($self, $af) $tie
+
+            $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?
+        }
+
+        $crate::functor_kind!($name<A $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?>);
+    };
+    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn consume($a:ident) $consume:block fn tie($self:ident, $af:ident) $tie:block $(fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block)?) => {
         paste! {
             $crate::prelude::functor! {
                 $name<A $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?>:
@@ -119,6 +208,8 @@ macro_rules! applicative {
                 fn fmap(self, f) {
                     self.tie(consume(f))
                 }
+
+                $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?
             }
 
             mod [<$name:snake _applicative_impl>] {
@@ -128,16 +219,16 @@ macro_rules! applicative {
 
                 impl<A: Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> Applicative<A> for $name<A $(, $($g_ty),+)?> {
                     type Applicative<B: Clone> = $name<B $(, $($g_ty),+)?>;
-                    #[inline(always)] #[must_use] fn consume(mut $a: A) -> Self $consume
-                    #[inline(always)] #[must_use] fn tie<F: FnOnce(A) -> B + Clone, B: Clone>(mut $self, mut $af: Self::Applicative<F>) -> Self::Applicative<B> $tie
+                    #[inline(always)] #[must_use] fn consume(mut $a: A) -> Self where A: 'static $consume
+                    #[inline(always)] #[must_use] fn tie<F: FnOnce(A) -> B + Clone + 'static, B: Clone + 'static>(mut $self, mut $af: Self::Applicative<F>) -> Self::Applicative<B> where A: 'static $tie
                 }
 
-                impl<A: Clone, B: Clone, F: FnOnce(A) -> B + Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::Mul<$name<F $(, $($g_ty),+)?>> for $name<A $(, $($g_ty),+)?> {
+                impl<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::Mul<$name<F $(, $($g_ty),+)?>> for $name<A $(, $($g_ty),+)?> {
                     type Output = $name<B $(, $($g_ty),+)?>;
                     #[inline(always)] #[must_use] fn mul(mut self, mut af: $name<F $(, $($g_ty),+)?>) -> Self::Output { self.tie(af) }
                 }
 
-                $crate::test_applicative!($name<u64>);
+                $crate::test_applicative!($name<u64> $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?);
             }
         }
     };
@@ -145,12 +236,14 @@ macro_rules! applicative {
 pub use applicative;
 
 /// Test the Alternative laws.
+/// With `fn observe(...) { ... }`, compares what `observe` returns instead of the values themselves (for types like `Stream` that can't implement `PartialEq`).
 #[macro_export]
 macro_rules! test_alternative {
     ($name:ident<u64>) => {
         quickcheck::quickcheck! {
             fn prop_monoid_associativity(ma: $name<u64>, mb: $name<u64>, mc: $name<u64>) -> bool {
-                ma.clone().either(|| mb.clone().either(|| mc.clone())) == ma.either(move || mb).either(move || mc)
+                let (lb, lc) = (mb.clone(), mc.clone());
+                ma.clone().either(move || lb.either(move || lc)) == ma.either(move || mb).either(move || mc)
             }
             fn prop_monoid_left_identity(ma: $name<u64>) -> bool {
                 ma.clone() == empty::<$name<u64>, _>().either(move || ma)
@@ -160,13 +253,33 @@ macro_rules! test_alternative {
             }
         }
     };
+    ($name:ident<u64> fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        $crate::test_alternative!(fn observe($m: $ty $(, $x: $x_ty)?) $observe);
+    };
+    (fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        quickcheck::quickcheck! {
+            fn prop_monoid_associativity(ma: $ty, mb: $ty, mc: $ty $(, $x: $x_ty)?) -> bool {
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                let (lb, lc) = (mb.clone(), mc.clone());
+                observe(ma.clone().either(move || lb.either(move || lc)) $(, $x.clone())?) == observe(ma.either(move || mb).either(move || mc) $(, $x)?)
+            }
+            fn prop_monoid_left_identity(ma: $ty $(, $x: $x_ty)?) -> bool {
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(ma.clone() $(, $x.clone())?) == observe(empty::<$ty, _>().either(move || ma) $(, $x)?)
+            }
+            fn prop_monoid_right_identity(ma: $ty $(, $x: $x_ty)?) -> bool {
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(ma.clone() $(, $x.clone())?) == observe(ma.either(empty::<$ty, _>) $(, $x)?)
+            }
+        }
+    };
 }
 pub use test_alternative;
 
 /// Implement ONLY `Alternative` (not its superclasses) after a definition.
 #[macro_export]
 macro_rules! just_alternative {
-    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn empty() $empty:block fn either($self:ident, $make_other:ident) $either:block) => {
+    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn empty() $empty:block fn either($self:ident, $make_other:ident) $either:block $(fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block)?) => {
         paste! {
             mod [<$name:snake _alternative_impl>] {
                 #![allow(unused_mut)]
@@ -177,16 +290,16 @@ macro_rules! just_alternative {
                 #[allow(clippy::missing_trait_methods)]
                 impl<A: Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> Alternative<A> for $name<A $(, $($g_ty),+)?> {
                     type Alternative<B: Clone> = $name<B $(, $($g_ty),+)?>;
-                    #[inline(always)] #[must_use] fn empty() -> Self $empty
-                    #[inline(always)] #[must_use] fn either<F: FnOnce() -> Self>(mut $self, mut $make_other: F) -> Self $either
+                    #[inline(always)] #[must_use] fn empty() -> Self where A: 'static $empty
+                    #[inline(always)] #[must_use] fn either<F: FnOnce() -> Self + 'static>(mut $self, mut $make_other: F) -> Self where A: 'static $either
                 }
 
-                impl<F: FnOnce() -> $name<A>, A: Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::BitOr<F> for $name<A $(, $($g_ty),+)?> {
+                impl<F: FnOnce() -> $name<A $(, $($g_ty),+)?> + 'static, A: Clone + 'static $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::BitOr<F> for $name<A $(, $($g_ty),+)?> {
                     type Output = $name<A $(, $($g_ty),+)?>;
                     #[inline(always)] #[must_use] fn bitor(mut self, mut make_other: F) -> $name<A $(, $($g_ty),+)?> { self.either(make_other) }
                 }
 
-                test_alternative!($name<u64>);
+                test_alternative!($name<u64> $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?);
             }
         }
     }
@@ -220,6 +333,7 @@ macro_rules! alternative {
 }
 
 /// Test the monad laws.
+/// With `fn observe(...) { ... }`, compares what `observe` returns instead of the values themselves (for types like `State` that can't implement `PartialEq`).
 #[macro_export]
 macro_rules! test_monad {
     ($name:ident<u64>) => {
@@ -240,6 +354,30 @@ macro_rules! test_monad {
             }
         }
     };
+    ($name:ident<u64> fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        $crate::test_monad!(fn observe($m: $ty $(, $x: $x_ty)?) $observe);
+    };
+    (fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        quickcheck::quickcheck! {
+            fn prop_monad_left_identity(a: u64 $(, $x: $x_ty)?) -> bool {
+                use $crate::entropy::hash_consume as f;
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(consume::<$ty, _>(a).bind(f) $(, $x.clone())?) == observe(f(a) $(, $x)?)
+            }
+            fn prop_monad_right_identity(ma: $ty $(, $x: $x_ty)?) -> bool {
+                #![allow(clippy::arithmetic_side_effects)]
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(ma.clone() $(, $x.clone())?) == observe(ma.bind(consume) $(, $x)?)
+            }
+            fn prop_monad_associativity(ma: $ty $(, $x: $x_ty)?) -> bool {
+                #![allow(clippy::arithmetic_side_effects)]
+                use $crate::entropy::hash_consume as g;
+                use $crate::entropy::reverse_consume as h;
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(ma.clone().bind(g).bind(h) $(, $x.clone())?) == observe(ma.bind(move |a| { let ga: $ty = g(a); ga.bind(h) }) $(, $x)?)
+            }
+        }
+    };
 }
 pub use test_monad;
 
@@ -273,7 +411,7 @@ pub use test_monad;
 /// ```
 #[macro_export]
 macro_rules! monad {
-    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn consume($a:ident) $consume:block fn bind($self:ident, $f:ident) $bind:block) => {
+    ($name:ident<A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn consume($a:ident) $consume:block fn bind($self:ident, $f:ident) $bind:block $(fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block)?) => {
         paste! {
             $crate::prelude::applicative! {
                 $name<A $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?>:
@@ -283,8 +421,12 @@ macro_rules! monad {
                 fn tie(self, af) {
                     self.bind(move |a| af.bind(move |f| consume(f(a))))
                 }
+
+                $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?
             }
 
+            $crate::functor_kind!($name<A $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?>);
+
             mod [<$name:snake _monad_impl>] {
                 #![allow(unused_mut)]
                 use $crate::prelude::*;
@@ -294,10 +436,10 @@ macro_rules! monad {
                 #[allow(clippy::missing_trait_methods)]
                 impl<A: Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> Monad<A> for $name<A $(, $($g_ty),+)?> {
                     type Monad<B: Clone> = $name<B $(, $($g_ty),+)?>;
-                    #[inline(always)] #[must_use] fn bind<B: Clone, F: FnOnce(A) -> $name<B $(, $($g_ty),+)?> + Clone>(mut $self, mut $f: F) -> $name<B $(, $($g_ty),+)?> $bind
+                    #[inline(always)] #[must_use] fn bind<B: Clone + 'static, F: FnOnce(A) -> $name<B $(, $($g_ty),+)?> + Clone + 'static>(mut $self, mut $f: F) -> $name<B $(, $($g_ty),+)?> where A: 'static $bind
                 }
 
-                impl<A: Clone, B: Clone, F: FnOnce(A) -> $name<B $(, $($g_ty),+)?> + Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::Shr<F> for $name<A $(, $($g_ty),+)?> {
+                impl<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> $name<B $(, $($g_ty),+)?> + Clone + 'static $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::Shr<F> for $name<A $(, $($g_ty),+)?> {
                     type Output = $name<B $(, $($g_ty),+)?>;
                     #[inline(always)] #[must_use] fn shr(mut self, mut f: F) -> $name<B $(, $($g_ty),+)?> { self.bind(f) }
                 }
@@ -307,14 +449,110 @@ macro_rules! monad {
                     #[inline(always)] #[must_use] fn bitand(mut self, mut other: Self) -> Self { self.seq(other) }
                 }
 
+                impl<X: Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> MonadKind for $name<X $(, $($g_ty),+)?> {
+                    type Of<A: Clone> = $name<A $(, $($g_ty),+)?>;
+                    #[inline(always)] fn consume_in<A: Clone + 'static>(a: A) -> $name<A $(, $($g_ty),+)?> { consume(a) }
+                    #[inline(always)] fn bind_in<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> $name<B $(, $($g_ty),+)?> + Clone + 'static>(m: $name<A $(, $($g_ty),+)?>, f: F) -> $name<B $(, $($g_ty),+)?> { m.bind(f) }
+                }
 
-                test_monad!($name<u64>);
+                test_monad!($name<u64> $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?);
             }
         }
     };
 }
 pub use monad;
 
+/// Test the indexed monad laws, with every index set to `u64`.
+///
+/// With `fn observe(...) { ... }`, compares what `observe` returns instead of the values themselves (for types like `IxState` that can't implement `PartialEq`).
+#[macro_export]
+macro_rules! test_ix_monad {
+    ($name:ident<u64, u64, u64>) => {
+        $crate::test_ix_monad!(fn observe(m: $name<u64, u64, u64>) { m });
+    };
+    ($name:ident<u64, u64, u64> fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        $crate::test_ix_monad!(fn observe($m: $ty $(, $x: $x_ty)?) $observe);
+    };
+    (fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block) => {
+        quickcheck::quickcheck! {
+            fn prop_ix_monad_left_identity(a: u64, mb: $ty $(, $x: $x_ty)?) -> bool {
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                let f = move |b: u64| -> $ty { if $crate::entropy::hash(b) & 1 == 0 { mb.clone() } else { ireturn(b) } };
+                observe(ireturn::<$ty, _, _>(a).ibind(f.clone()) $(, $x.clone())?) == observe(f(a) $(, $x)?)
+            }
+            fn prop_ix_monad_right_identity(ma: $ty $(, $x: $x_ty)?) -> bool {
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                observe(ma.clone() $(, $x.clone())?) == observe(ma.ibind(ireturn::<$ty, _, _>) $(, $x)?)
+            }
+            fn prop_ix_monad_associativity(ma: $ty, mb: $ty, mc: $ty $(, $x: $x_ty)?) -> bool {
+                fn observe($m: $ty $(, $x: $x_ty)?) -> impl PartialEq $observe
+                let g = move |b: u64| -> $ty { if $crate::entropy::hash(b) & 1 == 0 { mb.clone() } else { ireturn($crate::entropy::reverse(b)) } };
+                let h = move |c: u64| -> $ty { if $crate::entropy::hash(c) & 2 == 0 { mc.clone() } else { ireturn($crate::entropy::hash(c)) } };
+                observe(ma.clone().ibind(g.clone()).ibind(h.clone()) $(, $x.clone())?) == observe(ma.ibind(move |a| g(a).ibind(h)) $(, $x)?)
+            }
+        }
+    };
+}
+pub use test_ix_monad;
+
+/// Implement `IxMonad` and `>>` after a definition whose first three type parameters are the starting state, the ending state, and the value.
+/// ```rust
+/// use rsmonad::prelude::*;
+///
+/// // A value tagged with the state we're in, where every step just retags it:
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Tagged<I, J, A>(A, core::marker::PhantomData<(I, J)>);
+///
+/// ix_monad! {
+///     Tagged<I, J, A>:
+///
+///     fn ireturn(a) {
+///         Tagged(a, core::marker::PhantomData)
+///     }
+///
+///     fn ibind(self, f) {
+///         let Tagged(b, _) = f(self.0);
+///         Tagged(b, core::marker::PhantomData)
+///     }
+/// }
+///
+/// # fn main() {
+/// struct Cold;
+/// struct Warm;
+/// fn heat(x: u8) -> Tagged<Cold, Warm, u8> { Tagged(x + 1, core::marker::PhantomData) }
+/// let cold: Tagged<Cold, Cold, u8> = ireturn(1);
+/// assert_eq!((cold >> heat).0, 2);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ix_monad {
+    ($name:ident<I, J, A $(, $($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+)?>: fn ireturn($a:ident) $ireturn:block fn ibind($self:ident, $f:ident) $ibind:block $(fn observe($m:ident: $ty:ty $(, $x:ident: $x_ty:ty)?) $observe:block)?) => {
+        paste! {
+            mod [<$name:snake _ix_monad_impl>] {
+                #![allow(unused_mut)]
+                use $crate::prelude::*;
+                #[allow(unused_imports)]
+                use super::*;
+
+                #[allow(clippy::missing_trait_methods)]
+                impl<I, J, A: Clone $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> IxMonad<I, J, A> for $name<I, J, A $(, $($g_ty),+)?> {
+                    type IxMonad<K, L, B: Clone> = $name<K, L, B $(, $($g_ty),+)?>;
+                    #[inline(always)] #[must_use] fn ireturn(mut $a: A) -> $name<I, I, A $(, $($g_ty),+)?> where I: 'static, A: 'static $ireturn
+                    #[inline(always)] #[must_use] fn ibind<K: 'static, B: Clone + 'static, F: FnOnce(A) -> $name<J, K, B $(, $($g_ty),+)?> + Clone + 'static>(mut $self, mut $f: F) -> $name<I, K, B $(, $($g_ty),+)?> where I: 'static, J: 'static, A: 'static $ibind
+                }
+
+                impl<I: 'static, J: 'static, K: 'static, A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> $name<J, K, B $(, $($g_ty),+)?> + Clone + 'static $(, $($g_ty $(: $g_bound $(+ $g_bounds)*)?),+)?> core::ops::Shr<F> for $name<I, J, A $(, $($g_ty),+)?> {
+                    type Output = $name<I, K, B $(, $($g_ty),+)?>;
+                    #[inline(always)] #[must_use] fn shr(mut self, mut f: F) -> $name<I, K, B $(, $($g_ty),+)?> { self.ibind(f) }
+                }
+
+                test_ix_monad!($name<u64, u64, u64> $(fn observe($m: $ty $(, $x: $x_ty)?) $observe)?);
+            }
+        }
+    };
+}
+pub use ix_monad;
+
 /// Implement `Fold` (and its superclasses automatically) after a definition.
 /// ```rust
 /// use rsmonad::prelude::*;
@@ -331,7 +569,7 @@ pub use monad;
 /// ```
 #[macro_export]
 macro_rules! fold {
-    ($name:ident$(<$($g_ty:ident $(: $g_bound:path $(, $g_bounds:path)*)?),+>)?: type Item = $item:ty;) => {
+    ($name:ident$(<$($g_ty:ident $(: $g_bound:tt $(+ $g_bounds:tt)*)?),+>)?: type Item = $item:ty;) => {
         paste! {
             mod [<$name:snake _fold_impl>] {
                 use $crate::prelude::*;
@@ -94,7 +94,7 @@ impl<A: Clone> core::ops::Try for Maybe<A> {
     type Residual = Residual;
     #[inline]
     fn from_output(a: A) -> Self {
-        consume(a)
+        Just(a)
     }
     #[inline]
     fn branch(self) -> core::ops::ControlFlow<Residual, A> {
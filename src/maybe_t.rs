@@ -0,0 +1,248 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `MaybeT` monad transformer.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+#[cfg(feature = "alloc")]
+use crate::just_alternative;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use core::cell::{Cell, OnceCell};
+
+/// `Maybe` on top of any other monad `M` (written as e.g. `Vec<()>` for `Vec<_>`; see `MonadKind`).
+///
+/// Under the hood, just an `M` holding a `Maybe<A>`: `>>` runs `M`'s effects and stops at the first `Nothing`.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// // Nondeterminism (`List`) that can also fail (`Maybe`):
+/// fn halve(x: u8) -> MaybeT<u8, List<()>> {
+///     MaybeT::new(list![if x % 2 == 0 { Just(x / 2) } else { Nothing }])
+/// }
+/// let choices: MaybeT<u8, List<()>> = lift(list![4, 6, 8]);
+/// assert_eq!(
+///     (choices >> halve >> halve).run_maybe_t(),
+///     list![Just(1), Nothing, Just(2)],
+/// );
+/// # }
+/// ```
+pub struct MaybeT<A: Clone, M: MonadKind>(M::Of<Maybe<A>>);
+
+monad! {
+    MaybeT<A, M: MonadKind + 'static>:
+
+    fn consume(a) {
+        Self(M::consume_in(Just(a)))
+    }
+
+    fn bind(self, f) {
+        MaybeT(M::bind_in(self.0, move |ma| match ma {
+            Just(a) => f(a).0,
+            Nothing => M::consume_in(Nothing),
+        }))
+    }
+
+    fn observe(m: MaybeT<u64, Option<()>>) {
+        m.run_maybe_t()
+    }
+}
+
+// Needs `alloc`: the second alternative is built only once the first comes up empty, then shared by every clone of the continuation.
+#[cfg(feature = "alloc")]
+just_alternative! {
+    MaybeT<A, M: MonadKind + 'static>:
+
+    fn empty() {
+        Self(M::consume_in(Nothing))
+    }
+
+    fn either(self, make_other) {
+        let pending = Rc::new((Cell::new(Some(make_other)), OnceCell::new()));
+        MaybeT(M::bind_in(self.0, move |ma| match ma {
+            Just(a) => M::consume_in(Just(a)),
+            Nothing => pending.1.get_or_init(|| pending.0.take().map_or_else(empty, |f| f())).clone().0,
+        }))
+    }
+
+    fn observe(m: MaybeT<u64, Option<()>>) {
+        m.run_maybe_t()
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind> MonadTrans<A, M> for MaybeT<A, M> {
+    #[inline(always)]
+    fn lift(m: M::Of<A>) -> Self {
+        Self(M::bind_in(m, |a| M::consume_in(Just(a))))
+    }
+}
+
+impl<A: Clone, M: MonadKind> MaybeT<A, M> {
+    /// Wrap a `Maybe` inside the underlying monad.
+    #[inline(always)]
+    pub const fn new(m: M::Of<Maybe<A>>) -> Self {
+        Self(m)
+    }
+    /// Unwrap the underlying monad.
+    #[inline(always)]
+    pub fn run_maybe_t(self) -> M::Of<Maybe<A>> {
+        self.0
+    }
+}
+
+impl<X: Clone, S: Clone + 'static, M: MonadState<S> + 'static> MonadState<S> for MaybeT<X, M> {
+    #[inline(always)]
+    fn get() -> MaybeT<S, M> {
+        MaybeT::lift(M::get())
+    }
+    #[inline(always)]
+    fn put(s: S) -> MaybeT<(), M> {
+        MaybeT::lift(M::put(s))
+    }
+}
+
+impl<X: Clone, R: Clone + 'static, M: MonadReader<R> + 'static> MonadReader<R> for MaybeT<X, M> {
+    #[inline(always)]
+    fn ask() -> MaybeT<R, M> {
+        MaybeT::lift(M::ask())
+    }
+    #[inline(always)]
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: MaybeT<A, M>,
+    ) -> MaybeT<A, M> {
+        MaybeT(M::local(f, m.0))
+    }
+}
+
+impl<X: Clone, W: Monoid + Clone + 'static, M: MonadWriter<W> + 'static> MonadWriter<W>
+    for MaybeT<X, M>
+{
+    #[inline(always)]
+    fn tell(w: W) -> MaybeT<(), M> {
+        MaybeT::lift(M::tell(w))
+    }
+    #[inline(always)]
+    fn listen<A: Clone + 'static>(m: MaybeT<A, M>) -> MaybeT<(A, W), M> {
+        MaybeT(M::bind_in(M::listen(m.0), |(ma, w)| {
+            M::consume_in(match ma {
+                Just(a) => Just((a, w)),
+                Nothing => Nothing,
+            })
+        }))
+    }
+}
+
+impl<X: Clone, E: Clone + 'static, M: MonadError<E> + 'static> MonadError<E> for MaybeT<X, M> {
+    #[inline(always)]
+    fn throw<A: Clone + 'static>(e: E) -> MaybeT<A, M> {
+        MaybeT::lift(M::throw(e))
+    }
+    #[inline(always)]
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> MaybeT<A, M> + Clone + 'static>(
+        m: MaybeT<A, M>,
+        handler: F,
+    ) -> MaybeT<A, M> {
+        MaybeT(M::catch(m.0, move |e| handler(e).0))
+    }
+}
+
+impl<A: Clone, M: MonadKind> Clone for MaybeT<A, M> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<A: Clone, M: MonadKind> core::fmt::Debug for MaybeT<A, M>
+where
+    M::Of<Maybe<A>>: core::fmt::Debug,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("MaybeT").field(&self.0).finish()
+    }
+}
+
+impl<A: Clone, M: MonadKind> PartialEq for MaybeT<A, M>
+where
+    M::Of<Maybe<A>>: PartialEq,
+{
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<A: Clone + 'static, M: MonadKind + 'static> quickcheck::Arbitrary for MaybeT<A, M>
+where
+    M::Of<Maybe<A>>: quickcheck::Arbitrary,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self(M::Of::<Maybe<A>>::arbitrary(g))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod over_std {
+    //! Laws again, this time over some more interesting base monads.
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    mod over_vec {
+        use super::*;
+        crate::test_functor!(fn observe(m: MaybeT<u64, Vec<()>>) { m.run_maybe_t() });
+        crate::test_applicative!(fn observe(m: MaybeT<u64, Vec<()>>) { m.run_maybe_t() });
+        crate::test_monad!(fn observe(m: MaybeT<u64, Vec<()>>) { m.run_maybe_t() });
+        crate::test_alternative!(fn observe(m: MaybeT<u64, Vec<()>>) { m.run_maybe_t() });
+    }
+
+    mod over_list {
+        use super::*;
+        crate::test_functor!(fn observe(m: MaybeT<u64, List<()>>) { m.run_maybe_t() });
+        crate::test_applicative!(fn observe(m: MaybeT<u64, List<()>>) { m.run_maybe_t() });
+        crate::test_monad!(fn observe(m: MaybeT<u64, List<()>>) { m.run_maybe_t() });
+        crate::test_alternative!(fn observe(m: MaybeT<u64, List<()>>) { m.run_maybe_t() });
+    }
+
+    mod over_io {
+        use super::*;
+        crate::test_functor!(fn observe(m: MaybeT<u64, Io<()>>) { m.run_maybe_t().run() });
+        crate::test_applicative!(fn observe(m: MaybeT<u64, Io<()>>) { m.run_maybe_t().run() });
+        crate::test_monad!(fn observe(m: MaybeT<u64, Io<()>>) { m.run_maybe_t().run() });
+        crate::test_alternative!(fn observe(m: MaybeT<u64, Io<()>>) { m.run_maybe_t().run() });
+    }
+
+    #[test]
+    fn recursive_alternative() {
+        // Every attempt has another one waiting behind it, so building them all up front would never end.
+        fn first_even_from(n: u64) -> MaybeT<u64, Option<()>> {
+            let here = MaybeT::new(Some(if n.is_multiple_of(2) { Just(n) } else { Nothing }));
+            here | move || first_even_from(n + 1)
+        }
+        assert_eq!(first_even_from(7).run_maybe_t(), Some(Just(8)));
+
+        // And over a base monad that runs the fallback more than once, it's still only built once.
+        let builds = Rc::new(Cell::new(0_u8));
+        let counted = {
+            let counter = Rc::clone(&builds);
+            move || {
+                counter.set(counter.get() + 1);
+                MaybeT::<u8, List<()>>::new(list![Just(9)])
+            }
+        };
+        let either_way = MaybeT::new(list![Nothing, Just(1), Nothing]) | counted;
+        assert_eq!(either_way.run_maybe_t(), list![Just(9), Just(1), Just(9)]);
+        assert_eq!(builds.get(), 1);
+    }
+}
@@ -21,11 +21,19 @@ pub trait Monad<A: Clone>: Applicative<A> {
     /// Fucking pain in the ass redundancy. This has to be in this trait to avoid potential spooky action at a distance e.g. by redefining a separate Hkt later.
     type Monad<B: Clone>: Monad<B, Monad<A> = Self>;
     /// Mutate internal state with some function.
-    fn bind<B: Clone, F: FnOnce(A) -> Self::Monad<B> + Clone>(self, f: F) -> Self::Monad<B>;
+    fn bind<B: Clone + 'static, F: FnOnce(A) -> Self::Monad<B> + Clone + 'static>(
+        self,
+        f: F,
+    ) -> Self::Monad<B>
+    where
+        A: 'static;
     /// Flatten a nested monad into a single-layer monad.
     #[inline(always)]
-    fn join<Flat: Clone, MFlat: Monad<Flat, Monad<MFlat> = Self> + Clone>(self) -> MFlat
+    fn join<Flat: Clone + 'static, MFlat: Monad<Flat, Monad<MFlat> = Self> + Clone + 'static>(
+        self,
+    ) -> MFlat
     where
+        A: 'static,
         Self: Sized + Monad<MFlat, Monad<Flat> = MFlat>,
     {
         self.bind::<Flat, _>(core::convert::identity::<MFlat>)
@@ -43,7 +51,12 @@ pub trait Monad<A: Clone>: Applicative<A> {
 
 /// Mutate internal state with some function.
 #[inline(always)]
-pub fn bind<A: Clone, B: Clone, MA: Monad<A>, F: FnOnce(A) -> MA::Monad<B> + Clone>(
+pub fn bind<
+    A: Clone + 'static,
+    B: Clone + 'static,
+    MA: Monad<A>,
+    F: FnOnce(A) -> MA::Monad<B> + Clone + 'static,
+>(
     ma: MA,
     f: F,
 ) -> MA::Monad<B> {
@@ -79,7 +92,11 @@ pub fn bind<A: Clone, B: Clone, MA: Monad<A>, F: FnOnce(A) -> MA::Monad<B> + Clo
 /// -- and the middle argument is clearly id
 /// ```
 #[inline(always)]
-pub fn join<MMA: Monad<MA, Monad<A> = MA>, MA: Monad<A, Monad<MA> = MMA> + Clone, A: Clone>(
+pub fn join<
+    MMA: Monad<MA, Monad<A> = MA>,
+    MA: Monad<A, Monad<MA> = MMA> + Clone + 'static,
+    A: Clone + 'static,
+>(
     mma: MMA,
 ) -> MA {
     mma.join()
@@ -0,0 +1,44 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Monad transformers.
+
+/// A monad's type constructor on its own, without whatever it holds (the `m` in Haskell's `m a`).
+///
+/// Implemented for every monad at every type, so e.g. `Vec<()>` stands in for `Vec<_>` as a transformer's base.
+///
+/// `Monad::Monad<B>` can't do this job by itself: it only promises to get back to the type it started from, so a transformer couldn't prove that `Vec<Maybe<A>>` and `Vec<Maybe<B>>` come from the same `Vec`.
+pub trait MonadKind {
+    /// This monad holding an `A`.
+    type Of<A: Clone>: Clone;
+    /// `consume` in this monad.
+    fn consume_in<A: Clone + 'static>(a: A) -> Self::Of<A>;
+    /// `bind` in this monad.
+    fn bind_in<
+        A: Clone + 'static,
+        B: Clone + 'static,
+        F: FnOnce(A) -> Self::Of<B> + Clone + 'static,
+    >(
+        m: Self::Of<A>,
+        f: F,
+    ) -> Self::Of<B>;
+}
+
+/// Original Haskell definition:
+/// ```haskell
+/// class MonadTrans t where
+/// lift :: Monad m => m a -> t m a
+/// ```
+pub trait MonadTrans<A: Clone, M: MonadKind> {
+    /// Run an action in the underlying monad without any of this transformer's effects.
+    fn lift(m: M::Of<A>) -> Self;
+}
+
+/// Run an action in the underlying monad without any of this transformer's effects.
+#[inline(always)]
+pub fn lift<A: Clone, M: MonadKind, T: MonadTrans<A, M>>(m: M::Of<A>) -> T {
+    T::lift(m)
+}
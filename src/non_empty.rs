@@ -0,0 +1,190 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `NonEmpty` monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::vec::Vec;
+
+/// A list with at least one element, so things like `head`, `maximum`, and folding without a `unit` can't fail.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// let xs = NonEmpty::new(3_u8, vec![1, 4, 1, 5]);
+/// assert_eq!((*xs.head(), *xs.last()), (3, 5));
+/// assert_eq!((xs.clone().minimum(), xs.clone().maximum()), (1, 5));
+/// assert_eq!(xs.clone().fold1(|acc, x| acc + x), 14);
+/// assert_eq!(xs.clone() + NonEmpty::new(9, vec![]), NonEmpty::new(3, vec![1, 4, 1, 5, 9]));
+/// fn and_ten(x: u8) -> NonEmpty<u8> { NonEmpty::new(x, vec![10 * x]) }
+/// assert_eq!(
+///     NonEmpty::new(1, vec![2]) >> and_ten,
+///     NonEmpty::new(1, vec![10, 2, 20]),
+/// );
+/// assert_eq!(Maybe::<NonEmpty<u8>>::from(list![]), Nothing);
+/// assert_eq!(Maybe::from(list![1, 2]), Just(NonEmpty::new(1, vec![2])));
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, QuickCheck)]
+pub struct NonEmpty<A>(A, Vec<A>);
+
+monad! {
+    NonEmpty<A>:
+
+    fn consume(a) {
+        Self::new(a, Vec::new())
+    }
+
+    fn bind(self, f) {
+        let mut out = f.clone()(self.0);
+        for a in self.1 {
+            out.1.extend(f.clone()(a));
+        }
+        out
+    }
+}
+
+fold! {
+    NonEmpty<A>:
+
+    type Item = A;
+}
+
+impl<A> NonEmpty<A> {
+    /// One element followed by any number of others.
+    #[inline(always)]
+    pub const fn new(head: A, tail: Vec<A>) -> Self {
+        Self(head, tail)
+    }
+    /// First element.
+    #[inline(always)]
+    pub const fn head(&self) -> &A {
+        &self.0
+    }
+    /// Everything but the first element.
+    #[inline(always)]
+    pub fn tail(&self) -> &[A] {
+        &self.1
+    }
+    /// Last element.
+    #[inline(always)]
+    pub fn last(&self) -> &A {
+        self.1.last().unwrap_or(&self.0)
+    }
+    /// Largest element (the last one, if there's a tie).
+    #[inline(always)]
+    pub fn maximum(self) -> A
+    where
+        A: Ord,
+    {
+        self.fold1(core::cmp::max)
+    }
+    /// Smallest element (the first one, if there's a tie).
+    #[inline(always)]
+    pub fn minimum(self) -> A
+    where
+        A: Ord,
+    {
+        self.fold1(core::cmp::min)
+    }
+    /// Combine every element from left to right, starting with the first instead of a `unit`.
+    #[inline(always)]
+    pub fn fold1<F: FnMut(A, A) -> A>(self, f: F) -> A {
+        self.1.into_iter().fold(self.0, f)
+    }
+    /// Combine every element with `Monoid::combine`, never touching `unit`.
+    #[inline(always)]
+    pub fn sconcat(self) -> A
+    where
+        A: Monoid,
+    {
+        self.fold1(combine)
+    }
+    /// Append another non-empty list to the end of this one.
+    #[inline(always)]
+    #[must_use]
+    pub fn combine(mut self, other: Self) -> Self {
+        self.1.extend(other);
+        self
+    }
+}
+
+impl<A> core::ops::Add for NonEmpty<A> {
+    type Output = Self;
+    #[inline(always)]
+    #[must_use]
+    fn add(self, rhs: Self) -> Self {
+        self.combine(rhs)
+    }
+}
+
+impl<A> IntoIterator for NonEmpty<A> {
+    type Item = A;
+    type IntoIter = core::iter::Chain<core::iter::Once<A>, <Vec<A> as IntoIterator>::IntoIter>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        core::iter::once(self.0).chain(self.1)
+    }
+}
+
+impl<A> From<NonEmpty<A>> for Vec<A> {
+    #[inline(always)]
+    fn from(value: NonEmpty<A>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<A> From<Vec<A>> for Maybe<NonEmpty<A>> {
+    #[inline(always)]
+    fn from(mut value: Vec<A>) -> Self {
+        if value.is_empty() {
+            return Nothing;
+        }
+        let head = value.remove(0);
+        Just(NonEmpty::new(head, value))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A> From<NonEmpty<A>> for List<A> {
+    #[inline(always)]
+    fn from(value: NonEmpty<A>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A> From<List<A>> for Maybe<NonEmpty<A>> {
+    #[inline(always)]
+    fn from(value: List<A>) -> Self {
+        Vec::from(value).into()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_without_unit() {
+        // Order matters for non-commutative combinations:
+        let words = NonEmpty::new(list!['a'], vec![list!['b'], list!['c']]);
+        assert_eq!(words.clone().sconcat(), list!['a', 'b', 'c']);
+        assert_eq!(words.fold1(|acc, x| x + acc), list!['c', 'b', 'a']);
+        // Largest and smallest, compared as a whole:
+        let pairs = NonEmpty::new((1, 'x'), vec![(3, 'y'), (0, 'z')]);
+        assert_eq!(pairs.clone().maximum(), (3, 'y'));
+        assert_eq!(pairs.minimum(), (0, 'z'));
+        // And `Ord`'s own methods still mean what they always do:
+        assert_eq!(
+            NonEmpty::new(1, vec![]).max(NonEmpty::new(2, vec![])),
+            NonEmpty::new(2, vec![])
+        );
+    }
+}
@@ -21,6 +21,17 @@ impl<A: Clone> Functor<A> for Option<A> {
 }
 test_functor!(Option<u64>);
 
+impl<X: Clone> FunctorKind for Option<X> {
+    type Of<A: Clone> = Option<A>;
+    #[inline(always)]
+    fn fmap_in<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static>(
+        fa: Self::Of<A>,
+        f: F,
+    ) -> Self::Of<B> {
+        fa.fmap(f)
+    }
+}
+
 impl<A: Clone> Applicative<A> for Option<A> {
     type Applicative<B: Clone> = Option<B>;
     #[inline(always)]
@@ -28,10 +39,13 @@ impl<A: Clone> Applicative<A> for Option<A> {
         Some(a)
     }
     #[inline(always)]
-    fn tie<F: FnOnce(A) -> B + Clone, B: Clone>(
+    fn tie<F: FnOnce(A) -> B + Clone + 'static, B: Clone + 'static>(
         self,
         af: Self::Applicative<F>,
-    ) -> Self::Applicative<B> {
+    ) -> Self::Applicative<B>
+    where
+        A: 'static,
+    {
         self.bind(move |a| af.bind(move |f| consume(f(a))))
     }
 }
@@ -44,7 +58,10 @@ impl<A: Clone> Alternative<A> for Option<A> {
         None
     }
     #[inline(always)]
-    fn either<F: FnOnce() -> Self>(self, make_other: F) -> Self {
+    fn either<F: FnOnce() -> Self + 'static>(self, make_other: F) -> Self
+    where
+        A: 'static,
+    {
         if matches!(self, Some(_)) {
             self
         } else {
@@ -63,6 +80,25 @@ impl<A: Clone> Monad<A> for Option<A> {
 }
 test_monad!(Option<u64>);
 
+impl<X: Clone> MonadKind for Option<X> {
+    type Of<A: Clone> = Option<A>;
+    #[inline(always)]
+    fn consume_in<A: Clone + 'static>(a: A) -> Self::Of<A> {
+        consume(a)
+    }
+    #[inline(always)]
+    fn bind_in<
+        A: Clone + 'static,
+        B: Clone + 'static,
+        F: FnOnce(A) -> Self::Of<B> + Clone + 'static,
+    >(
+        m: Self::Of<A>,
+        f: F,
+    ) -> Self::Of<B> {
+        m.bind(f)
+    }
+}
+
 impl<A> Fold for Option<A> {
     type Item = A;
 }
@@ -100,6 +136,17 @@ impl<A: Clone> Functor<A> for core::task::Poll<A> {
     }
 }
 
+impl<X: Clone> FunctorKind for core::task::Poll<X> {
+    type Of<A: Clone> = core::task::Poll<A>;
+    #[inline(always)]
+    fn fmap_in<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static>(
+        fa: Self::Of<A>,
+        f: F,
+    ) -> Self::Of<B> {
+        fa.fmap(f)
+    }
+}
+
 impl<A: Clone> Applicative<A> for core::task::Poll<A> {
     type Applicative<B: Clone> = core::task::Poll<B>;
     #[inline(always)]
@@ -107,10 +154,13 @@ impl<A: Clone> Applicative<A> for core::task::Poll<A> {
         Self::Ready(a)
     }
     #[inline(always)]
-    fn tie<F: FnOnce(A) -> B + Clone, B: Clone>(
+    fn tie<F: FnOnce(A) -> B + Clone + 'static, B: Clone + 'static>(
         self,
         af: Self::Applicative<F>,
-    ) -> Self::Applicative<B> {
+    ) -> Self::Applicative<B>
+    where
+        A: 'static,
+    {
         self.bind(move |a| af.bind(move |f: F| consume(f(a))))
     }
 }
@@ -122,7 +172,10 @@ impl<A: Clone> Alternative<A> for core::task::Poll<A> {
         Self::Pending
     }
     #[inline(always)]
-    fn either<F: FnOnce() -> Self>(self, make_other: F) -> Self {
+    fn either<F: FnOnce() -> Self + 'static>(self, make_other: F) -> Self
+    where
+        A: 'static,
+    {
         if self.is_ready() {
             self
         } else {
@@ -142,3 +195,22 @@ impl<A: Clone> Monad<A> for core::task::Poll<A> {
         }
     }
 }
+
+impl<X: Clone> MonadKind for core::task::Poll<X> {
+    type Of<A: Clone> = core::task::Poll<A>;
+    #[inline(always)]
+    fn consume_in<A: Clone + 'static>(a: A) -> Self::Of<A> {
+        consume(a)
+    }
+    #[inline(always)]
+    fn bind_in<
+        A: Clone + 'static,
+        B: Clone + 'static,
+        F: FnOnce(A) -> Self::Of<B> + Clone + 'static,
+    >(
+        m: Self::Of<A>,
+        f: F,
+    ) -> Self::Of<B> {
+        m.bind(f)
+    }
+}
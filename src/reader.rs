@@ -0,0 +1,141 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Reader` monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::rc::Rc;
+
+/// Computations that read from a shared, read-only environment of type `R` (e.g. configuration).
+/// Under the hood, just a function `&R -> A`, so the environment is only ever borrowed.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "alloc")] {
+/// struct Config {
+///     verbose: bool,
+///     name: &'static str,
+/// }
+/// fn greeting(punctuation: char) -> Reader<String, Config> {
+///     Reader::asks(|c: &Config| c.name) >> move |name| {
+///         Reader::asks(move |c: &Config| {
+///             if c.verbose {
+///                 format!("Hello, {name}, how are you{punctuation}")
+///             } else {
+///                 format!("Hi{punctuation}")
+///             }
+///         })
+///     }
+/// }
+/// let config = Config { verbose: true, name: "Will" };
+/// assert_eq!(greeting('?').run_reader(&config), "Hello, Will, how are you?");
+/// assert_eq!(
+///     greeting('!').local(|c| Config { verbose: false, ..*c }).run_reader(&config),
+///     "Hi!",
+/// );
+/// # }
+/// ```
+pub struct Reader<A, R>(Rc<dyn Fn(&R) -> A>);
+
+monad! {
+    Reader<A, R: 'static>:
+
+    fn consume(a) {
+        Self(Rc::new(move |_| a.clone()))
+    }
+
+    fn bind(self, f) {
+        Reader(Rc::new(move |r| (f.clone()(self.run_reader(r)).0)(r)))
+    }
+
+    fn observe(m: Reader<u64, u64>, r: u64) {
+        m.run_reader(&r)
+    }
+}
+
+impl<A, R: 'static> Reader<A, R> {
+    /// Wrap a function reading from the environment.
+    #[inline(always)]
+    pub fn new<F: Fn(&R) -> A + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run this computation with a borrowed environment.
+    #[inline(always)]
+    pub fn run_reader(&self, r: &R) -> A {
+        (self.0)(r)
+    }
+    /// Read some function of the environment.
+    #[inline(always)]
+    pub fn asks<F: Fn(&R) -> A + 'static>(f: F) -> Self {
+        Self::new(f)
+    }
+    /// Run this computation in a modified copy of the environment.
+    #[inline(always)]
+    #[must_use]
+    pub fn local<F: Fn(&R) -> R + 'static>(self, f: F) -> Self
+    where
+        A: 'static,
+    {
+        Self::new(move |r| self.run_reader(&f(r)))
+    }
+}
+
+impl<R: Clone + 'static> Reader<R, R> {
+    /// Read the whole environment.
+    #[inline(always)]
+    #[must_use]
+    pub fn ask() -> Self {
+        Self::new(R::clone)
+    }
+}
+
+impl<X: Clone, R: Clone + 'static> MonadReader<R> for Reader<X, R> {
+    #[inline(always)]
+    fn ask() -> Reader<R, R> {
+        Reader::ask()
+    }
+    #[inline(always)]
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: Reader<A, R>,
+    ) -> Reader<A, R> {
+        m.local(f)
+    }
+}
+
+impl<A, R> Clone for Reader<A, R> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A, R> core::fmt::Debug for Reader<A, R> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Reader(<function>)")
+    }
+}
+
+impl<A: quickcheck::Arbitrary, R: core::hash::Hash + 'static> quickcheck::Arbitrary
+    for Reader<A, R>
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // Pick one of two values depending on the environment.
+        let even = A::arbitrary(g);
+        let odd = A::arbitrary(g);
+        Self::new(move |r| {
+            if hash(r) & 1 == 0 {
+                even.clone()
+            } else {
+                odd.clone()
+            }
+        })
+    }
+}
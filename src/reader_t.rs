@@ -0,0 +1,239 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `ReaderT` monad transformer.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::rc::Rc;
+
+/// `Reader` on top of any other monad `M` (written as e.g. `Vec<()>` for `Vec<_>`; see `MonadKind`).
+///
+/// Under the hood, just a function `&R -> M<A>`.
+/// Unlike `Reader`, the environment has to be `Clone`, since `M` may hold onto the rest of the computation after the borrow ends.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// // Every multiple of the environment up to some bound:
+/// type Multiples = ReaderT<u8, u8, List<()>>;
+/// fn multiples(bound: u8) -> Multiples {
+///     Multiples::ask() >> move |step| Multiples::lift(list![1..bound]) % move |k| k * step
+/// }
+/// assert_eq!(multiples(4).run_reader_t(&10), list![10, 20, 30]);
+/// assert_eq!(
+///     multiples(4).local(|step| step + 1).run_reader_t(&10),
+///     list![11, 22, 33],
+/// );
+/// # }
+/// ```
+pub struct ReaderT<A: Clone, R, M: MonadKind>(Rc<dyn Fn(&R) -> M::Of<A>>);
+
+monad! {
+    ReaderT<A, R: Clone + 'static, M: MonadKind + 'static>:
+
+    fn consume(a) {
+        Self::new(move |_| M::consume_in(a.clone()))
+    }
+
+    fn bind(self, f) {
+        ReaderT::new(move |r: &R| {
+            let (f, env) = (f.clone(), r.clone());
+            M::bind_in(self.run_reader_t(r), move |a| f(a).run_reader_t(&env))
+        })
+    }
+
+    fn observe(m: ReaderT<u64, u64, Option<()>>, r: u64) {
+        m.run_reader_t(&r)
+    }
+}
+
+impl<A: Clone + 'static, R: 'static, M: MonadKind + 'static> MonadTrans<A, M> for ReaderT<A, R, M> {
+    #[inline(always)]
+    fn lift(m: M::Of<A>) -> Self {
+        Self::new(move |_| m.clone())
+    }
+}
+
+impl<A: Clone, R: 'static, M: MonadKind + 'static> ReaderT<A, R, M> {
+    /// Wrap a function reading from the environment into the underlying monad.
+    #[inline(always)]
+    pub fn new<F: Fn(&R) -> M::Of<A> + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run this computation with a borrowed environment.
+    #[inline(always)]
+    pub fn run_reader_t(&self, r: &R) -> M::Of<A> {
+        (self.0)(r)
+    }
+    /// Run this computation in a modified copy of the environment.
+    #[inline(always)]
+    #[must_use]
+    pub fn local<F: Fn(&R) -> R + 'static>(self, f: F) -> Self
+    where
+        A: 'static,
+    {
+        Self::new(move |r| self.run_reader_t(&f(r)))
+    }
+}
+
+impl<A: Clone + 'static, R: 'static, M: MonadKind + 'static> ReaderT<A, R, M> {
+    /// Read some function of the environment.
+    #[inline(always)]
+    pub fn asks<F: Fn(&R) -> A + 'static>(f: F) -> Self {
+        Self::new(move |r| M::consume_in(f(r)))
+    }
+}
+
+impl<R: Clone + 'static, M: MonadKind + 'static> ReaderT<R, R, M> {
+    /// Read the whole environment.
+    #[inline(always)]
+    #[must_use]
+    pub fn ask() -> Self {
+        Self::asks(R::clone)
+    }
+}
+
+impl<X: Clone, R: Clone + 'static, M: MonadKind + 'static> MonadReader<R> for ReaderT<X, R, M> {
+    #[inline(always)]
+    fn ask() -> ReaderT<R, R, M> {
+        ReaderT::ask()
+    }
+    #[inline(always)]
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: ReaderT<A, R, M>,
+    ) -> ReaderT<A, R, M> {
+        m.local(f)
+    }
+}
+
+impl<X: Clone, R: Clone + 'static, S: Clone + 'static, M: MonadState<S> + 'static> MonadState<S>
+    for ReaderT<X, R, M>
+{
+    #[inline(always)]
+    fn get() -> ReaderT<S, R, M> {
+        ReaderT::lift(M::get())
+    }
+    #[inline(always)]
+    fn put(s: S) -> ReaderT<(), R, M> {
+        ReaderT::lift(M::put(s))
+    }
+}
+
+impl<X: Clone, R: Clone + 'static, W: Monoid + Clone + 'static, M: MonadWriter<W> + 'static>
+    MonadWriter<W> for ReaderT<X, R, M>
+{
+    #[inline(always)]
+    fn tell(w: W) -> ReaderT<(), R, M> {
+        ReaderT::lift(M::tell(w))
+    }
+    #[inline(always)]
+    fn listen<A: Clone + 'static>(m: ReaderT<A, R, M>) -> ReaderT<(A, W), R, M> {
+        ReaderT::new(move |r| M::listen(m.run_reader_t(r)))
+    }
+}
+
+impl<X: Clone, R: Clone + 'static, E: Clone + 'static, M: MonadError<E> + 'static> MonadError<E>
+    for ReaderT<X, R, M>
+{
+    #[inline(always)]
+    fn throw<A: Clone + 'static>(e: E) -> ReaderT<A, R, M> {
+        ReaderT::lift(M::throw(e))
+    }
+    #[inline(always)]
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> ReaderT<A, R, M> + Clone + 'static>(
+        m: ReaderT<A, R, M>,
+        handler: F,
+    ) -> ReaderT<A, R, M> {
+        ReaderT::new(move |r: &R| {
+            let (recover, env) = (handler.clone(), r.clone());
+            M::catch(m.run_reader_t(r), move |e| recover(e).run_reader_t(&env))
+        })
+    }
+}
+
+impl<A: Clone, R, M: MonadKind> Clone for ReaderT<A, R, M> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A: Clone, R, M: MonadKind> core::fmt::Debug for ReaderT<A, R, M> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ReaderT(<function>)")
+    }
+}
+
+impl<A: Clone + 'static, R: core::hash::Hash + 'static, M: MonadKind + 'static>
+    quickcheck::Arbitrary for ReaderT<A, R, M>
+where
+    M::Of<A>: quickcheck::Arbitrary,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // Pick one of two effects in the underlying monad depending on the environment.
+        let even = M::Of::<A>::arbitrary(g);
+        let odd = M::Of::<A>::arbitrary(g);
+        Self::new(move |r| {
+            if hash(r) & 1 == 0 {
+                even.clone()
+            } else {
+                odd.clone()
+            }
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod over_std {
+    //! Laws again, this time over some more interesting base monads.
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    mod over_list {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: ReaderT<u64, u64, List<()>>, r: u64) {
+                m.run_reader_t(&r)
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: ReaderT<u64, u64, List<()>>, r: u64) {
+                m.run_reader_t(&r)
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: ReaderT<u64, u64, List<()>>, r: u64) {
+                m.run_reader_t(&r)
+            }
+        );
+    }
+
+    mod over_state {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: ReaderT<u64, u64, State<(), u64>>, rs: (u64, u64)) {
+                m.run_reader_t(&rs.0).run_state(rs.1)
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: ReaderT<u64, u64, State<(), u64>>, rs: (u64, u64)) {
+                m.run_reader_t(&rs.0).run_state(rs.1)
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: ReaderT<u64, u64, State<(), u64>>, rs: (u64, u64)) {
+                m.run_reader_t(&rs.0).run_state(rs.1)
+            }
+        );
+    }
+}
@@ -0,0 +1,236 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Rws` (reader-writer-state) monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::rc::Rc;
+
+/// `Reader`, `Writer`, and `State` in one: reads a borrowed environment `R`, logs a monoid `W`, and threads a state `S`.
+/// Under the hood, just a function `(&R, S) -> (A, S, W)`.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// // Count down by a step size read from the environment, logging each step.
+/// fn tick((): ()) -> Rws<(), u8, SumU8, u8> {
+///     Rws::ask() >> |step: u8| Rws::modify(move |n: u8| n - step) >> move |()| Rws::tell(SumU8(step))
+/// }
+/// assert_eq!(
+///     (tick(()) >> tick >> tick >> |()| Rws::get()).run_rws(&2, 10),
+///     (4, 4, SumU8(6)),
+/// );
+/// ```
+pub struct Rws<A, R, W, S>(Rc<dyn Fn(&R, S) -> (A, S, W)>);
+
+monad! {
+    Rws<A, R: 'static, W: Monoid + 'static, S: 'static>:
+
+    fn consume(a) {
+        Self::new(move |_, s| (a.clone(), s, unit()))
+    }
+
+    fn bind(self, f) {
+        Rws::new(move |r, s| {
+            let (a, t, w) = (self.0)(r, s);
+            let (b, u, v) = (f.clone()(a).0)(r, t);
+            (b, u, w.combine(v))
+        })
+    }
+
+    fn observe(m: Rws<u64, u64, SumU8, u64>, rs: (u64, u64)) {
+        m.run_rws(&rs.0, rs.1)
+    }
+}
+
+impl<A, R: 'static, W: 'static, S: 'static> Rws<A, R, W, S> {
+    /// Wrap a function from an environment and a state to a value, a new state, and a log.
+    #[inline(always)]
+    pub fn new<F: Fn(&R, S) -> (A, S, W) + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run this computation with a borrowed environment and an initial state, returning the final value, the final state, and the log.
+    #[inline(always)]
+    pub fn run_rws(self, r: &R, s: S) -> (A, S, W) {
+        (self.0)(r, s)
+    }
+    /// Run this computation and keep only the final value and the log.
+    #[inline(always)]
+    pub fn eval_rws(self, r: &R, s: S) -> (A, W) {
+        let (a, _, w) = self.run_rws(r, s);
+        (a, w)
+    }
+    /// Run this computation and keep only the final state and the log.
+    #[inline(always)]
+    pub fn exec_rws(self, r: &R, s: S) -> (S, W) {
+        let (_, t, w) = self.run_rws(r, s);
+        (t, w)
+    }
+    /// Run this computation in a modified copy of the environment.
+    #[inline(always)]
+    #[must_use]
+    pub fn local<F: Fn(&R) -> R + 'static>(self, f: F) -> Self
+    where
+        A: 'static,
+    {
+        Self::new(move |r, s| (self.0)(&f(r), s))
+    }
+    /// Modify everything logged by this computation with some function.
+    #[inline(always)]
+    #[must_use]
+    pub fn censor<F: Fn(W) -> W + 'static>(self, f: F) -> Self
+    where
+        A: 'static,
+    {
+        Self::new(move |r, s| {
+            let (a, t, w) = (self.0)(r, s);
+            (a, t, f(w))
+        })
+    }
+}
+
+impl<A, R: 'static, W: Monoid + 'static, S: 'static> Rws<A, R, W, S> {
+    /// Read some function of the environment.
+    #[inline(always)]
+    pub fn asks<F: Fn(&R) -> A + 'static>(f: F) -> Self {
+        Self::new(move |r, s| (f(r), s, unit()))
+    }
+    /// Read some function of the current state without changing it.
+    #[inline(always)]
+    pub fn gets<F: Fn(&S) -> A + 'static>(f: F) -> Self {
+        Self::new(move |_, s| (f(&s), s, unit()))
+    }
+}
+
+impl<R: Clone + 'static, W: Monoid + 'static, S: 'static> Rws<R, R, W, S> {
+    /// Read the whole environment.
+    #[inline(always)]
+    #[must_use]
+    pub fn ask() -> Self {
+        Self::asks(R::clone)
+    }
+}
+
+impl<R: 'static, W: Monoid + 'static, S: Clone + 'static> Rws<S, R, W, S> {
+    /// Read the current state without changing it.
+    #[inline(always)]
+    #[must_use]
+    pub fn get() -> Self {
+        Self::gets(S::clone)
+    }
+}
+
+impl<R: 'static, W: Monoid + 'static, S: 'static> Rws<(), R, W, S> {
+    /// Log a value.
+    #[inline(always)]
+    pub fn tell(w: W) -> Self
+    where
+        W: Clone,
+    {
+        Self::new(move |_, s| ((), s, w.clone()))
+    }
+    /// Replace the current state.
+    #[inline(always)]
+    pub fn put(s: S) -> Self
+    where
+        S: Clone,
+    {
+        Self::new(move |_, _| ((), s.clone(), unit()))
+    }
+    /// Replace the current state with some function of it.
+    #[inline(always)]
+    pub fn modify<F: Fn(S) -> S + 'static>(f: F) -> Self {
+        Self::new(move |_, s| ((), f(s), unit()))
+    }
+}
+
+impl<X: Clone, R: 'static, W: Monoid + 'static, S: Clone + 'static> MonadState<S>
+    for Rws<X, R, W, S>
+{
+    #[inline(always)]
+    fn get() -> Rws<S, R, W, S> {
+        Rws::get()
+    }
+    #[inline(always)]
+    fn put(s: S) -> Rws<(), R, W, S> {
+        Rws::put(s)
+    }
+}
+
+impl<X: Clone, R: Clone + 'static, W: Monoid + 'static, S: 'static> MonadReader<R>
+    for Rws<X, R, W, S>
+{
+    #[inline(always)]
+    fn ask() -> Rws<R, R, W, S> {
+        Rws::ask()
+    }
+    #[inline(always)]
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: Rws<A, R, W, S>,
+    ) -> Rws<A, R, W, S> {
+        m.local(f)
+    }
+}
+
+impl<X: Clone, R: 'static, W: Monoid + Clone + 'static, S: 'static> MonadWriter<W>
+    for Rws<X, R, W, S>
+{
+    #[inline(always)]
+    fn tell(w: W) -> Rws<(), R, W, S> {
+        Rws::tell(w)
+    }
+    #[inline(always)]
+    fn listen<A: Clone + 'static>(m: Rws<A, R, W, S>) -> Rws<(A, W), R, W, S> {
+        Rws::new(move |r, s| {
+            let (a, t, w) = (m.0)(r, s);
+            ((a, w.clone()), t, w)
+        })
+    }
+}
+
+impl<A, R, W, S> Clone for Rws<A, R, W, S> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A, R, W, S> core::fmt::Debug for Rws<A, R, W, S> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Rws(<function>)")
+    }
+}
+
+impl<
+        A: quickcheck::Arbitrary,
+        R: core::hash::Hash + 'static,
+        W: quickcheck::Arbitrary,
+        S: quickcheck::Arbitrary,
+    > quickcheck::Arbitrary for Rws<A, R, W, S>
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // Pick one of two values depending on the environment, log something, and either leave the state alone or overwrite it.
+        let even = A::arbitrary(g);
+        let odd = A::arbitrary(g);
+        let w = W::arbitrary(g);
+        let overwrite = Option::<S>::arbitrary(g);
+        Self::new(move |r, s| {
+            (
+                if hash(r) & 1 == 0 {
+                    even.clone()
+                } else {
+                    odd.clone()
+                },
+                overwrite.clone().unwrap_or(s),
+                w.clone(),
+            )
+        })
+    }
+}
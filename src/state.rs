@@ -0,0 +1,137 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `State` monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::rc::Rc;
+
+/// Threads a value of type `S` through a chain of computations without any mutable variables.
+/// Under the hood, just a function `S -> (A, S)`: take the current state, return a value and the new state.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")] {
+/// // Label each character with a running counter:
+/// fn label(c: char) -> State<(u8, char), u8> {
+///     State::get() >> move |n: u8| State::put(n + 1) >> move |()| consume((n, c))
+/// }
+/// assert_eq!(
+///     (label('a') >> |x| label('b') >> move |y| consume(list![x, y])).run_state(0),
+///     (list![(0, 'a'), (1, 'b')], 2),
+/// );
+/// # }
+/// ```
+pub struct State<A, S>(Rc<dyn Fn(S) -> (A, S)>);
+
+monad! {
+    State<A, S: 'static>:
+
+    fn consume(a) {
+        Self(Rc::new(move |s| (a.clone(), s)))
+    }
+
+    fn bind(self, f) {
+        State(Rc::new(move |s| {
+            let (a, t) = (self.0)(s);
+            (f.clone()(a).0)(t)
+        }))
+    }
+
+    fn observe(m: State<u64, u64>, s: u64) {
+        m.run_state(s)
+    }
+}
+
+impl<A, S: 'static> State<A, S> {
+    /// Wrap a function from the current state to a value and a new state.
+    #[inline(always)]
+    pub fn new<F: Fn(S) -> (A, S) + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run this computation from some initial state, returning both the final value and the final state.
+    #[inline(always)]
+    pub fn run_state(self, s: S) -> (A, S) {
+        (self.0)(s)
+    }
+    /// Run this computation from some initial state and keep only the final value.
+    #[inline(always)]
+    pub fn eval_state(self, s: S) -> A {
+        self.run_state(s).0
+    }
+    /// Run this computation from some initial state and keep only the final state.
+    #[inline(always)]
+    pub fn exec_state(self, s: S) -> S {
+        self.run_state(s).1
+    }
+    /// Read some function of the current state without changing it.
+    #[inline(always)]
+    pub fn gets<F: Fn(&S) -> A + 'static>(f: F) -> Self {
+        Self::new(move |s| (f(&s), s))
+    }
+}
+
+impl<S: Clone + 'static> State<S, S> {
+    /// Read the current state without changing it.
+    #[inline(always)]
+    #[must_use]
+    pub fn get() -> Self {
+        Self::new(|s: S| (s.clone(), s))
+    }
+}
+
+impl<S: 'static> State<(), S> {
+    /// Replace the current state.
+    #[inline(always)]
+    pub fn put(s: S) -> Self
+    where
+        S: Clone,
+    {
+        Self::new(move |_| ((), s.clone()))
+    }
+    /// Replace the current state with some function of it.
+    #[inline(always)]
+    pub fn modify<F: Fn(S) -> S + 'static>(f: F) -> Self {
+        Self::new(move |s| ((), f(s)))
+    }
+}
+
+impl<X: Clone, S: Clone + 'static> MonadState<S> for State<X, S> {
+    #[inline(always)]
+    fn get() -> State<S, S> {
+        State::get()
+    }
+    #[inline(always)]
+    fn put(s: S) -> State<(), S> {
+        State::put(s)
+    }
+}
+
+impl<A, S> Clone for State<A, S> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A, S> core::fmt::Debug for State<A, S> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("State(<function>)")
+    }
+}
+
+impl<A: quickcheck::Arbitrary, S: quickcheck::Arbitrary> quickcheck::Arbitrary for State<A, S> {
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // Either leave the state alone or overwrite it, then return a constant.
+        let a = A::arbitrary(g);
+        let overwrite = Option::<S>::arbitrary(g);
+        Self::new(move |s| (a.clone(), overwrite.clone().unwrap_or(s)))
+    }
+}
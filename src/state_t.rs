@@ -0,0 +1,274 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `StateT` monad transformer.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+use alloc::rc::Rc;
+
+/// `State` on top of any other monad `M` (written as e.g. `Vec<()>` for `Vec<_>`; see `MonadKind`).
+///
+/// Under the hood, just a function `S -> M<(A, S)>`, so with e.g. `List` as the base, each branch of the search gets its own state.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// // Draw a number from a pool without putting it back:
+/// fn draw() -> StateT<u8, List<u8>, List<()>> {
+///     StateT::new(|pool: List<u8>| {
+///         pool.clone()
+///             .into_iter()
+///             .map(|x| {
+///                 let mut rest = pool.clone();
+///                 rest.retain(|&y| y != x);
+///                 (x, rest)
+///             })
+///             .collect()
+///     })
+/// }
+/// let pairs = draw() >> |a| draw() >> move |b| consume((a, b));
+/// assert_eq!(
+///     pairs.eval_state_t(list![1, 2, 3]),
+///     list![(1, 2), (1, 3), (2, 1), (2, 3), (3, 1), (3, 2)],
+/// );
+/// # }
+/// ```
+pub struct StateT<A: Clone, S: Clone, M: MonadKind>(Rc<dyn Fn(S) -> M::Of<(A, S)>>);
+
+monad! {
+    StateT<A, S: Clone + 'static, M: MonadKind + 'static>:
+
+    fn consume(a) {
+        Self::new(move |s| M::consume_in((a.clone(), s)))
+    }
+
+    fn bind(self, f) {
+        StateT::new(move |s| {
+            let f = f.clone();
+            M::bind_in((self.0)(s), move |(a, t)| (f(a).0)(t))
+        })
+    }
+
+    fn observe(m: StateT<u64, u64, Option<()>>, s: u64) {
+        m.run_state_t(s)
+    }
+}
+
+impl<A: Clone + 'static, S: Clone + 'static, M: MonadKind + 'static> MonadTrans<A, M>
+    for StateT<A, S, M>
+{
+    #[inline(always)]
+    fn lift(m: M::Of<A>) -> Self {
+        Self::new(move |s| M::bind_in(m.clone(), move |a| M::consume_in((a, s))))
+    }
+}
+
+impl<A: Clone + 'static, S: Clone + 'static, M: MonadKind + 'static> StateT<A, S, M> {
+    /// Wrap a function from the current state to a value and a new state inside the underlying monad.
+    #[inline(always)]
+    pub fn new<F: Fn(S) -> M::Of<(A, S)> + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+    /// Run this computation from some initial state, returning both the final value and the final state inside the underlying monad.
+    #[inline(always)]
+    pub fn run_state_t(self, s: S) -> M::Of<(A, S)> {
+        (self.0)(s)
+    }
+    /// Run this computation from some initial state and keep only the final value.
+    #[inline(always)]
+    pub fn eval_state_t(self, s: S) -> M::Of<A> {
+        M::bind_in(self.run_state_t(s), |(a, _)| M::consume_in(a))
+    }
+    /// Run this computation from some initial state and keep only the final state.
+    #[inline(always)]
+    pub fn exec_state_t(self, s: S) -> M::Of<S> {
+        M::bind_in(self.run_state_t(s), |(_, t)| M::consume_in(t))
+    }
+    /// Read some function of the current state without changing it.
+    #[inline(always)]
+    pub fn gets<F: Fn(&S) -> A + 'static>(f: F) -> Self {
+        Self::new(move |s| M::consume_in((f(&s), s)))
+    }
+}
+
+impl<S: Clone + 'static, M: MonadKind + 'static> StateT<S, S, M> {
+    /// Read the current state without changing it.
+    #[inline(always)]
+    #[must_use]
+    pub fn get() -> Self {
+        Self::gets(S::clone)
+    }
+}
+
+impl<S: Clone + 'static, M: MonadKind + 'static> StateT<(), S, M> {
+    /// Replace the current state.
+    #[inline(always)]
+    pub fn put(s: S) -> Self {
+        Self::new(move |_| M::consume_in(((), s.clone())))
+    }
+    /// Replace the current state with some function of it.
+    #[inline(always)]
+    pub fn modify<F: Fn(S) -> S + 'static>(f: F) -> Self {
+        Self::new(move |s| M::consume_in(((), f(s))))
+    }
+}
+
+impl<X: Clone, S: Clone + 'static, M: MonadKind + 'static> MonadState<S> for StateT<X, S, M> {
+    #[inline(always)]
+    fn get() -> StateT<S, S, M> {
+        StateT::get()
+    }
+    #[inline(always)]
+    fn put(s: S) -> StateT<(), S, M> {
+        StateT::put(s)
+    }
+}
+
+impl<X: Clone, S: Clone + 'static, R: Clone + 'static, M: MonadReader<R> + 'static> MonadReader<R>
+    for StateT<X, S, M>
+{
+    #[inline(always)]
+    fn ask() -> StateT<R, S, M> {
+        StateT::lift(M::ask())
+    }
+    #[inline(always)]
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: StateT<A, S, M>,
+    ) -> StateT<A, S, M> {
+        StateT::new(move |s| M::local(f.clone(), m.clone().run_state_t(s)))
+    }
+}
+
+impl<X: Clone, S: Clone + 'static, W: Monoid + Clone + 'static, M: MonadWriter<W> + 'static>
+    MonadWriter<W> for StateT<X, S, M>
+{
+    #[inline(always)]
+    fn tell(w: W) -> StateT<(), S, M> {
+        StateT::lift(M::tell(w))
+    }
+    #[inline(always)]
+    fn listen<A: Clone + 'static>(m: StateT<A, S, M>) -> StateT<(A, W), S, M> {
+        StateT::new(move |s| {
+            M::bind_in(M::listen(m.clone().run_state_t(s)), |((a, t), w)| {
+                M::consume_in(((a, w), t))
+            })
+        })
+    }
+}
+
+impl<X: Clone, S: Clone + 'static, E: Clone + 'static, M: MonadError<E> + 'static> MonadError<E>
+    for StateT<X, S, M>
+{
+    #[inline(always)]
+    fn throw<A: Clone + 'static>(e: E) -> StateT<A, S, M> {
+        StateT::lift(M::throw(e))
+    }
+    #[inline(always)]
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> StateT<A, S, M> + Clone + 'static>(
+        m: StateT<A, S, M>,
+        handler: F,
+    ) -> StateT<A, S, M> {
+        // The handler starts over from the state we had before `m`.
+        StateT::new(move |s: S| {
+            let (recover, before) = (handler.clone(), s.clone());
+            M::catch(m.clone().run_state_t(s), move |e| {
+                recover(e).run_state_t(before)
+            })
+        })
+    }
+}
+
+impl<A: Clone, S: Clone, M: MonadKind> Clone for StateT<A, S, M> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A: Clone, S: Clone, M: MonadKind> core::fmt::Debug for StateT<A, S, M> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("StateT(<function>)")
+    }
+}
+
+impl<A: quickcheck::Arbitrary, S: quickcheck::Arbitrary, M: MonadKind + 'static>
+    quickcheck::Arbitrary for StateT<A, S, M>
+where
+    M::Of<(A, Option<S>)>: quickcheck::Arbitrary,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        // Some arbitrary effect in the underlying monad, where each result either leaves the state alone or overwrites it.
+        let m = M::Of::<(A, Option<S>)>::arbitrary(g);
+        Self::new(move |s| {
+            M::bind_in(m.clone(), move |(a, overwrite)| {
+                M::consume_in((a, overwrite.unwrap_or(s)))
+            })
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod over_std {
+    //! Laws again, this time over some more interesting base monads.
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    mod over_list {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: StateT<u64, u64, List<()>>, s: u64) {
+                m.run_state_t(s)
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: StateT<u64, u64, List<()>>, s: u64) {
+                m.run_state_t(s)
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: StateT<u64, u64, List<()>>, s: u64) {
+                m.run_state_t(s)
+            }
+        );
+    }
+
+    mod over_io {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: StateT<u64, u64, Io<()>>, s: u64) {
+                m.run_state_t(s).run()
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: StateT<u64, u64, Io<()>>, s: u64) {
+                m.run_state_t(s).run()
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: StateT<u64, u64, Io<()>>, s: u64) {
+                m.run_state_t(s).run()
+            }
+        );
+    }
+
+    #[test]
+    fn lift_and_state() {
+        // Each branch of the list gets its own copy of the counter.
+        let branch: StateT<u8, u8, List<()>> = lift(list![1, 10]);
+        let program = branch >> |x| StateT::modify(move |n: u8| n + x) >> move |()| StateT::get();
+        assert_eq!(program.run_state_t(5), list![(6, 6), (15, 15)]);
+        let failing: StateT<u8, u8, Hazard<(), &str>> =
+            StateT::put(3) >> |()| lift(Failure("nope"));
+        assert_eq!(failing.run_state_t(0), Failure("nope"));
+    }
+}
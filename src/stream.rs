@@ -0,0 +1,330 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Stream` monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::{just_alternative, prelude::*};
+use alloc::{boxed::Box, collections::VecDeque, rc::Rc, vec::Vec};
+use core::cell::{Cell, OnceCell};
+
+/// Like `List`, but lazy: nothing is computed until someone asks for it, so streams can be infinite.
+/// Each element is computed at most once, no matter how many clones of the stream look at it.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// // Infinite search, but we only ever compute the first three answers:
+/// let triples = stream![1_u32..] >> |z| {
+///     stream![{ 1 }..z] >> move |y| {
+///         stream![{ 1 }..y] >> move |x| {
+///             guard::<Stream<()>>(x * x + y * y == z * z) >> move |()| stream![(x, y, z)]
+///         }
+///     }
+/// };
+/// assert_eq!(
+///     List::from(triples.take(3)),
+///     list![(3, 4, 5), (6, 8, 10), (5, 12, 13)],
+/// );
+/// # }
+/// ```
+pub struct Stream<A>(Rc<Node<A>>);
+
+/// Deferred computation of the first element and the rest of a stream.
+type Thunk<A> = Box<dyn FnOnce() -> Option<(A, Stream<A>)>>;
+
+/// One cell of a stream: a thunk until forced, then its result.
+struct Node<A> {
+    /// Computation to run the first time we're forced.
+    thunk: Cell<Option<Thunk<A>>>,
+    /// Result of the thunk, once forced.
+    forced: OnceCell<Option<(A, Stream<A>)>>,
+}
+
+/// Initialize an `rsmonad` Stream.
+/// Takes the same syntax as `list!`, plus open-ended ranges for infinite streams.
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// assert_eq!(List::from(stream![1, 2, 3]), list![1, 2, 3]);
+/// assert_eq!(List::from(stream![1..4]), list![1, 2, 3]);
+/// assert_eq!(List::from(stream![1..].take(3)), list![1, 2, 3]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! stream {
+    ($a:literal..)=>{$crate::prelude::Stream::from_iter_lazy($a..)};
+    ({$a:expr}..)=>{$crate::prelude::Stream::from_iter_lazy($a..)};
+    ($a:literal..$b:expr)=>{$crate::prelude::Stream::from_iter_lazy($a..$b)};
+    ({$a:expr}..$b:expr)=>{$crate::prelude::Stream::from_iter_lazy($a..$b)};
+    ($($e:expr),*) => {
+        $crate::prelude::Stream::from_iter_lazy([$($e),*].into_iter())
+    };
+}
+pub use stream;
+
+impl<A: 'static> Stream<A> {
+    /// Defer computing the first element and the rest of the stream until someone asks for it.
+    #[inline(always)]
+    fn lazy<F: FnOnce() -> Option<(A, Self)> + 'static>(f: F) -> Self {
+        Self(Rc::new(Node {
+            thunk: Cell::new(Some(Box::new(f))),
+            forced: OnceCell::new(),
+        }))
+    }
+    /// Prepend an element to a stream.
+    #[inline(always)]
+    pub fn cons(a: A, tail: Self) -> Self {
+        Self(Rc::new(Node {
+            thunk: Cell::new(None),
+            forced: OnceCell::from(Some((a, tail))),
+        }))
+    }
+    /// Lazily wrap an iterator: elements are pulled out of it only when the stream needs them.
+    #[inline(always)]
+    pub fn from_iter_lazy<I: Iterator<Item = A> + 'static>(mut iter: I) -> Self {
+        Self::lazy(move || iter.next().map(|a| (a, Self::from_iter_lazy(iter))))
+    }
+}
+
+impl<A> Stream<A> {
+    /// Compute the first element and the rest of the stream, if we haven't already.
+    #[inline]
+    fn force(&self) -> Option<&(A, Self)> {
+        self.0
+            .forced
+            .get_or_init(|| self.0.thunk.take().map_or_else(|| None, |f| f()))
+            .as_ref()
+    }
+    /// Check if this stream is empty (forces the first element).
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.force().is_none()
+    }
+}
+
+impl<A: Clone + 'static> Stream<A> {
+    /// First element and the rest of the stream, if any.
+    #[inline(always)]
+    fn next(&self) -> Option<(A, Self)> {
+        self.force().cloned()
+    }
+    /// Split off the first element, if any.
+    #[inline(always)]
+    #[must_use]
+    pub fn uncons(&self) -> Maybe<(A, Self)> {
+        self.next().into()
+    }
+    /// Everything in this stream followed by everything in another.
+    #[inline(always)]
+    #[must_use]
+    pub fn append(self, other: Self) -> Self {
+        Self::lazy(move || match self.next() {
+            Some((a, rest)) => Some((a, rest.append(other))),
+            None => other.next(),
+        })
+    }
+    /// Keep at most the first `n` elements.
+    #[inline(always)]
+    #[must_use]
+    pub fn take(self, n: usize) -> Self {
+        Self::lazy(move || {
+            if n == 0 {
+                None
+            } else {
+                self.next()
+                    .map(|(a, rest)| (a, rest.take(n.saturating_sub(1))))
+            }
+        })
+    }
+    /// `a`, `f(a)`, `f(f(a))`, and so on forever.
+    #[inline(always)]
+    pub fn iterate<F: Fn(&A) -> A + 'static>(a: A, f: F) -> Self {
+        Self::lazy(move || {
+            let next = f(&a);
+            Some((a, Self::iterate(next, f)))
+        })
+    }
+    /// The same value forever.
+    #[inline(always)]
+    pub fn repeat(a: A) -> Self {
+        Self::iterate(a, A::clone)
+    }
+    /// This whole stream, over and over forever (or empty if it's empty).
+    #[inline(always)]
+    #[must_use]
+    pub fn cycle(self) -> Self {
+        Self::lazy(move || {
+            self.next()
+                .map(move |(a, rest)| (a, rest.append(Self::lazy(move || self.cycle().next()))))
+        })
+    }
+}
+
+monad! {
+    Stream<A>:
+
+    fn consume(a) {
+        Self::cons(a, empty())
+    }
+
+    fn bind(self, f) {
+        Stream::lazy(move || {
+            // Loop instead of recursing so long runs of empty results can't blow the stack.
+            let mut rest = self;
+            while let Some((a, tail)) = rest.next() {
+                if let Some((b, more)) = f.clone()(a).next() {
+                    return Some((b, more.append(tail.bind(f))));
+                }
+                rest = tail;
+            }
+            None
+        })
+    }
+
+    fn observe(m: Stream<u64>) {
+        m.take(64).into_iter().collect::<Vec<_>>()
+    }
+}
+
+just_alternative! {
+    Stream<A>:
+
+    fn empty() {
+        Self(Rc::new(Node {
+            thunk: Cell::new(None),
+            forced: OnceCell::from(None),
+        }))
+    }
+
+    fn either(self, make_other) {
+        self.append(Self::lazy(move || make_other().next()))
+    }
+
+    fn observe(m: Stream<u64>) {
+        m.take(64).into_iter().collect::<Vec<_>>()
+    }
+}
+
+fold! {
+    Stream<A: Clone + 'static>:
+
+    type Item = A;
+}
+
+/// Iterator over a `Stream`: lazy from the front, but going backward has to compute (and buffer) everything.
+#[allow(clippy::module_name_repetitions)]
+pub struct StreamIter<A> {
+    /// Whatever we haven't looked at yet.
+    front: Stream<A>,
+    /// Everything after `front`, once `next_back` has forced it.
+    back: VecDeque<A>,
+}
+
+impl<A: Clone + 'static> Iterator for StreamIter<A> {
+    type Item = A;
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        match self.front.next() {
+            Some((a, rest)) => {
+                self.front = rest;
+                Some(a)
+            }
+            None => self.back.pop_front(),
+        }
+    }
+}
+
+impl<A: Clone + 'static> DoubleEndedIterator for StreamIter<A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A> {
+        while let Some((a, rest)) = self.front.next() {
+            self.back.push_back(a);
+            self.front = rest;
+        }
+        self.back.pop_back()
+    }
+}
+
+impl<A: Clone + 'static> IntoIterator for Stream<A> {
+    type Item = A;
+    type IntoIter = StreamIter<A>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        StreamIter {
+            front: self,
+            back: VecDeque::new(),
+        }
+    }
+}
+
+impl<A: 'static> FromIterator<A> for Stream<A> {
+    #[inline(always)]
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        Self::from_iter_lazy(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+impl<A: Clone + 'static> From<Stream<A>> for Vec<A> {
+    #[inline(always)]
+    fn from(value: Stream<A>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: Clone + 'static> From<Stream<A>> for List<A> {
+    #[inline(always)]
+    fn from(value: Stream<A>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: 'static> From<List<A>> for Stream<A> {
+    #[inline(always)]
+    fn from(value: List<A>) -> Self {
+        Self::from_iter_lazy(value.into_iter())
+    }
+}
+
+impl<A> Drop for Node<A> {
+    #[inline]
+    fn drop(&mut self) {
+        // Unlink forced tails one at a time instead of recursively so long streams can't blow the stack.
+        let mut next = self.forced.take().flatten().map(|(_, tail)| tail);
+        while let Some(Stream(rc)) = next {
+            next = Rc::try_unwrap(rc)
+                .ok()
+                .and_then(|mut node| node.forced.take().flatten().map(|(_, tail)| tail));
+        }
+    }
+}
+
+impl<A> Clone for Stream<A> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A> core::fmt::Debug for Stream<A> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Stream(<lazy>)")
+    }
+}
+
+impl<A: quickcheck::Arbitrary> quickcheck::Arbitrary for Stream<A> {
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Vec::<A>::arbitrary(g).into_iter().collect()
+    }
+}
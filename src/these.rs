@@ -0,0 +1,121 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `These` monad.
+
+#![allow(clippy::mismatching_type_param_order)]
+
+use crate::prelude::*;
+
+/// Inclusive or: an `E` (e.g. warnings), an `A`, or both at once.
+///
+/// `>>` keeps going as long as there's a value and combines every `E` along the way with `Monoid::combine`.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// fn parse_port(s: &'static str) -> These<u16, List<String>> {
+///     match s.parse::<u16>() {
+///         Ok(p) if p < 1024 => Both(list![format!("{p} is privileged")], p),
+///         Ok(p) => That(p),
+///         Err(e) => This(list![e.to_string()]),
+///     }
+/// }
+/// fn double(p: u16) -> These<u16, List<String>> {
+///     p.checked_mul(2).map_or_else(|| This(list!["overflow".to_owned()]), That)
+/// }
+/// assert_eq!(parse_port("8080") >> double, That(16160));
+/// assert_eq!(
+///     parse_port("80") >> double,
+///     Both(list!["80 is privileged".to_owned()], 160),
+/// );
+/// assert_eq!(
+///     parse_port("40000") >> double,
+///     This(list!["overflow".to_owned()]),
+/// );
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, QuickCheck)]
+pub enum These<A, E> {
+    /// Only an `E`. Invoking `>>` will immediately return this as well.
+    This(E),
+    /// Only a value. Invoking `>>` on some function `f` will call `f` with that value as its argument.
+    That(A),
+    /// Both an `E` and a value. Invoking `>>` on some function `f` will call `f` with that value and combine the `E` with whatever `f` returns.
+    Both(E, A),
+}
+pub use These::{Both, That, This};
+
+monad! {
+    These<A, E: Monoid + Clone + 'static>:
+
+    fn consume(a) {
+        That(a)
+    }
+
+    fn bind(self, f) {
+        match self {
+            This(e) => This(e),
+            That(a) => f(a),
+            Both(e, a) => match f(a) {
+                This(more) => This(e.combine(more)),
+                That(b) => Both(e, b),
+                Both(more, b) => Both(e.combine(more), b),
+            },
+        }
+    }
+
+    fn observe(m: These<u64, SumU8>) {
+        m
+    }
+}
+
+impl<A, E> These<A, E> {
+    /// Map both sides at once.
+    #[inline]
+    pub fn bimap<B, F, FA: FnOnce(A) -> B, FE: FnOnce(E) -> F>(
+        self,
+        fa: FA,
+        fe: FE,
+    ) -> These<B, F> {
+        match self {
+            This(e) => This(fe(e)),
+            That(a) => That(fa(a)),
+            Both(e, a) => Both(fe(e), fa(a)),
+        }
+    }
+    /// Map the value, leaving any `E` alone.
+    #[inline(always)]
+    pub fn map_that<B, FA: FnOnce(A) -> B>(self, fa: FA) -> These<B, E> {
+        self.bimap(fa, core::convert::identity)
+    }
+    /// Map the `E`, leaving any value alone.
+    #[inline(always)]
+    pub fn map_this<F, FE: FnOnce(E) -> F>(self, fe: FE) -> These<A, F> {
+        self.bimap(core::convert::identity, fe)
+    }
+}
+
+impl<A: Clone, E: Clone> From<These<A, E>> for Hazard<A, E> {
+    #[inline(always)]
+    fn from(value: These<A, E>) -> Self {
+        match value {
+            This(e) => Failure(e),
+            That(a) | Both(_, a) => Success(a),
+        }
+    }
+}
+
+impl<A, E> From<These<A, E>> for Maybe<A> {
+    #[inline(always)]
+    fn from(value: These<A, E>) -> Self {
+        match value {
+            This(_) => Nothing,
+            That(a) | Both(_, a) => Just(a),
+        }
+    }
+}
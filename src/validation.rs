@@ -0,0 +1,84 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Validation` applicative.
+
+use crate::prelude::*;
+
+/// Like `Hazard`, but `*` keeps going after a failure and combines every failure it finds with `Monoid::combine`.
+///
+/// Deliberately not a monad: `>>` would have to stop at the first failure (there's no value to pass along), which would contradict `*`.
+/// If you need `>>`, convert to a `Hazard` with `.into()`.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// fn positive(x: i8) -> Validation<i8, List<String>> {
+///     if x > 0 {
+///         Valid(x)
+///     } else {
+///         Invalid(list![format!("{x} is not positive")])
+///     }
+/// }
+/// assert_eq!(
+///     positive(1) * (positive(2) % |a| move |b| a + b),
+///     Valid(3),
+/// );
+/// assert_eq!(
+///     positive(-1) * (positive(-2) % |a| move |b| a + b),
+///     Invalid(list!["-1 is not positive".to_owned(), "-2 is not positive".to_owned()]),
+/// );
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, QuickCheck)]
+pub enum Validation<A, E> {
+    /// Every failure found so far. Invoking `*` will add any failures on the other side.
+    Invalid(E),
+    /// A value that hasn't failed (yet).
+    Valid(A),
+}
+pub use Validation::{Invalid, Valid};
+
+applicative! {
+    Validation<A, E: Monoid + Clone + 'static>:
+
+    fn consume(a) {
+        Valid(a)
+    }
+
+    fn tie(self, af) {
+        match (self, af) {
+            (Valid(a), Valid(f)) => Valid(f(a)),
+            (Valid(_), Invalid(e)) | (Invalid(e), Valid(_)) => Invalid(e),
+            (Invalid(e), Invalid(more)) => Invalid(e.combine(more)),
+        }
+    }
+
+    fn observe(m: Validation<u64, SumU8>) {
+        m
+    }
+}
+
+impl<A: Clone, E: Clone> From<Hazard<A, E>> for Validation<A, E> {
+    #[inline(always)]
+    fn from(value: Hazard<A, E>) -> Self {
+        match value {
+            Success(a) => Valid(a),
+            Failure(e) => Invalid(e),
+        }
+    }
+}
+
+impl<A: Clone, E: Clone> From<Validation<A, E>> for Hazard<A, E> {
+    #[inline(always)]
+    fn from(value: Validation<A, E>) -> Self {
+        match value {
+            Valid(a) => Success(a),
+            Invalid(e) => Failure(e),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Console actions for `Io`, run against a swappable `Console`.
+
+use crate::prelude::*;
+use core::cell::RefCell;
+use std::{
+    collections::VecDeque,
+    io::{self, Write as _},
+    rc::Rc,
+};
+
+/// Somewhere `Io`'s console actions (`get_line_stdin`, `put_line`, etc.) can run.
+///
+/// By default, that's the real terminal (`RealConsole`),
+/// but `Io::with_console` runs a program against any other, e.g. a `ScriptedConsole` for tests (or just use `Io::run_scripted`).
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// let greet = put("Name? ") >> |()| get_line_stdin() >> |line: String| match line.trim() {
+///     "" => put_line_stderr("no name given"),
+///     name => put_line(format!("Hi, {name}!")),
+/// };
+///
+/// let (_, console) = greet.clone().run_scripted(["Ferris"]);
+/// assert_eq!(console.stdout(), list!["Name? Hi, Ferris!".to_owned()]);
+///
+/// let (_, console) = greet.run_scripted(Vec::<String>::new());
+/// assert_eq!(console.stdout(), list!["Name? ".to_owned()]);
+/// assert_eq!(console.stderr(), list!["no name given".to_owned()]);
+/// ```
+pub trait Console {
+    /// Read a line of input, including its newline (or nothing at the end of input).
+    /// # Errors
+    /// If input can't be read.
+    fn read_line(&self) -> io::Result<String>;
+    /// Write to standard output.
+    /// # Errors
+    /// If output can't be written.
+    fn write_stdout(&self, s: &str) -> io::Result<()>;
+    /// Write to standard error.
+    /// # Errors
+    /// If output can't be written.
+    fn write_stderr(&self, s: &str) -> io::Result<()>;
+}
+
+/// The real terminal, via `std::io`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct RealConsole;
+
+impl Console for RealConsole {
+    #[inline]
+    fn read_line(&self) -> io::Result<String> {
+        let mut s = String::new();
+        io::stdin().read_line(&mut s).map(|_| s)
+    }
+    #[inline]
+    fn write_stdout(&self, s: &str) -> io::Result<()> {
+        #![allow(clippy::question_mark_used)]
+        // Flush right away so prompts without a newline show up before we wait for input.
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(s.as_bytes())?;
+        stdout.flush()
+    }
+    #[inline]
+    fn write_stderr(&self, s: &str) -> io::Result<()> {
+        io::stderr().write_all(s.as_bytes())
+    }
+}
+
+/// A console with input written ahead of time, recording everything written to it.
+///
+/// Clones share the same input and output, so keep one around to check what a program did after running it against another.
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedConsole(Rc<RefCell<Script>>);
+
+/// Whatever's left to read and everything written so far.
+#[derive(Debug, Default)]
+struct Script {
+    /// Lines not yet read.
+    stdin: VecDeque<String>,
+    /// Everything written to standard output.
+    stdout: String,
+    /// Everything written to standard error.
+    stderr: String,
+}
+
+impl ScriptedConsole {
+    /// A console that will read these lines, in order, then reach the end of its input.
+    #[inline]
+    pub fn new<I: IntoIterator<Item = S>, S: Into<String>>(stdin: I) -> Self {
+        Self(Rc::new(RefCell::new(Script {
+            stdin: stdin.into_iter().map(Into::into).collect(),
+            ..Script::default()
+        })))
+    }
+    /// Every line written to standard output so far.
+    #[inline]
+    #[must_use]
+    pub fn stdout(&self) -> List<String> {
+        self.0.borrow().stdout.lines().map(str::to_owned).collect()
+    }
+    /// Every line written to standard error so far.
+    #[inline]
+    #[must_use]
+    pub fn stderr(&self) -> List<String> {
+        self.0.borrow().stderr.lines().map(str::to_owned).collect()
+    }
+}
+
+impl Console for ScriptedConsole {
+    #[inline]
+    fn read_line(&self) -> io::Result<String> {
+        // Just like a terminal: each line comes with its newline, and the end of input is an empty string.
+        Ok(self
+            .0
+            .borrow_mut()
+            .stdin
+            .pop_front()
+            .map_or_else(String::new, |mut line| {
+                line.push('\n');
+                line
+            }))
+    }
+    #[inline]
+    fn write_stdout(&self, s: &str) -> io::Result<()> {
+        self.0.borrow_mut().stdout.push_str(s);
+        Ok(())
+    }
+    #[inline]
+    fn write_stderr(&self, s: &str) -> io::Result<()> {
+        self.0.borrow_mut().stderr.push_str(s);
+        Ok(())
+    }
+}
+
+std::thread_local! {
+    /// Where console actions run right now.
+    static CURRENT: RefCell<Rc<dyn Console>> = RefCell::new(Rc::new(RealConsole));
+}
+
+/// Puts the previous console back once `Io::with_console` is done, even if its program panics.
+struct Restore(Rc<dyn Console>);
+
+impl Drop for Restore {
+    #[inline]
+    fn drop(&mut self) {
+        let previous: Rc<dyn Console> = Rc::clone(&self.0);
+        CURRENT.with(|current| current.replace(previous));
+    }
+}
+
+/// Run a console action against whichever console is current.
+fn on_console<A: Clone, F: Fn(&dyn Console) -> io::Result<A> + 'static>(f: F) -> IoResult<A> {
+    IoResult::attempt(move || {
+        let console = CURRENT.with(|current| Rc::clone(&current.borrow()));
+        f(&*console)
+    })
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl<A: 'static> Io<A> {
+    /// Run every console action in this program against another console (e.g. a `ScriptedConsole`) instead of the real terminal.
+    #[inline]
+    #[must_use]
+    pub fn with_console<C: Console + 'static>(self, console: C) -> Self {
+        let shared: Rc<dyn Console> = Rc::new(console);
+        Self::new(move || {
+            let _restore = Restore(CURRENT.with(|current| current.replace(Rc::clone(&shared))));
+            self.clone().run()
+        })
+    }
+    /// Run this program with these lines as its input, returning its value and the console with everything it wrote.
+    #[inline]
+    pub fn run_scripted<I: IntoIterator<Item = S>, S: Into<String>>(
+        self,
+        stdin: I,
+    ) -> (A, ScriptedConsole) {
+        let console = ScriptedConsole::new(stdin);
+        (self.with_console(console.clone()).run(), console)
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl<A: Clone + 'static, E: Clone + 'static> IoResult<A, E> {
+    /// Run every console action in this program against another console (e.g. a `ScriptedConsole`) instead of the real terminal.
+    #[inline]
+    #[must_use]
+    pub fn with_console<C: Console + 'static>(self, console: C) -> Self {
+        Self::new(self.run_io_result().with_console(console))
+    }
+    /// Run this program with these lines as its input, returning its outcome and the console with everything it wrote.
+    #[inline]
+    pub fn run_scripted<I: IntoIterator<Item = S>, S: Into<String>>(
+        self,
+        stdin: I,
+    ) -> (Hazard<A, E>, ScriptedConsole) {
+        self.run_io_result().run_scripted(stdin)
+    }
+}
+
+/// Reads a single line from `stdin`.
+#[must_use]
+#[inline(always)]
+pub fn get_line_stdin() -> IoResult<String> {
+    on_console(|console| console.read_line())
+}
+
+/// Prints without a newline.
+#[inline(always)]
+pub fn put<S: core::fmt::Display + 'static>(s: S) -> IoResult<()> {
+    on_console(move |console| console.write_stdout(&s.to_string()))
+}
+
+/// Prints with a newline.
+#[inline(always)]
+pub fn put_line<S: core::fmt::Display + 'static>(s: S) -> IoResult<()> {
+    on_console(move |console| console.write_stdout(&format!("{s}\n")))
+}
+
+/// Prints to `stderr` without a newline.
+#[inline(always)]
+pub fn put_stderr<S: core::fmt::Display + 'static>(s: S) -> IoResult<()> {
+    on_console(move |console| console.write_stderr(&s.to_string()))
+}
+
+/// Prints to `stderr` with a newline.
+#[inline(always)]
+pub fn put_line_stderr<S: core::fmt::Display + 'static>(s: S) -> IoResult<()> {
+    on_console(move |console| console.write_stderr(&format!("{s}\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_transcript() {
+        let echo = |line: String| put_line(line.trim().to_owned());
+        let session = put("got: ")
+            >> move |()| {
+                get_line_stdin()
+                    >> echo
+                    >> move |()| {
+                        put_line_stderr("warning: echoing")
+                            >> move |()| get_line_stdin() >> echo >> |()| get_line_stdin()
+                    }
+            };
+
+        // Nothing is read or written until it's run...
+        let console = ScriptedConsole::new(["one", "two"]);
+        let program = session.with_console(console.clone());
+        assert_eq!(console.stdout(), list![]);
+
+        // ...and then input runs out like a real terminal's would.
+        assert_eq!(program.run(), Success(String::new()));
+        assert_eq!(
+            console.stdout(),
+            list!["got: one".to_owned(), "two".to_owned()]
+        );
+        assert_eq!(console.stderr(), list!["warning: echoing".to_owned()]);
+    }
+}
@@ -0,0 +1,593 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Filesystem actions for `Io`, run against a swappable `FileSystem`.
+
+use crate::prelude::*;
+use core::cell::RefCell;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// Somewhere `Io`'s file actions (`read_file`, `write_file`, etc.) can run.
+///
+/// By default, that's the real filesystem (`RealFileSystem`),
+/// but `Io::with_file_system` runs a program against any other, e.g. a `FakeFileSystem` in memory for tests.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// let backup = read_file("notes.txt") >> |text: String| write_file("notes.bak", text);
+///
+/// let fake: FakeFileSystem = [("notes.txt", "remember the milk")].into_iter().collect();
+/// assert_eq!(backup.with_file_system(fake.clone()).run(), Success(()));
+/// assert_eq!(fake.files().get(std::path::Path::new("notes.bak")).map(String::as_str), Some("remember the milk"));
+/// ```
+pub trait FileSystem {
+    /// Read a whole file as text.
+    /// # Errors
+    /// If the file doesn't exist or can't be read as text.
+    fn read_file(&self, path: &Path) -> io::Result<String>;
+    /// Replace a file's contents, creating it if it doesn't exist.
+    /// # Errors
+    /// If the file can't be created or written.
+    fn write_file(&self, path: &Path, contents: &str) -> io::Result<()>;
+    /// Add to the end of a file, creating it if it doesn't exist.
+    /// # Errors
+    /// If the file can't be created or written.
+    fn append_file(&self, path: &Path, contents: &str) -> io::Result<()>;
+    /// Everything directly inside a directory, sorted.
+    /// # Errors
+    /// If the directory doesn't exist or can't be read.
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+    /// Make a directory, along with any missing directories above it.
+    /// # Errors
+    /// If something that isn't a directory is in the way, or it can't be created.
+    fn create_dir(&self, path: &Path) -> io::Result<()>;
+    /// Delete a file.
+    /// # Errors
+    /// If the file doesn't exist or can't be deleted.
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+    /// Whether a file or directory exists.
+    fn exists(&self, path: &Path) -> bool;
+}
+
+/// The real filesystem, via `std::fs`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct RealFileSystem;
+
+impl FileSystem for RealFileSystem {
+    #[inline]
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+    #[inline]
+    fn write_file(&self, path: &Path, contents: &str) -> io::Result<()> {
+        std::fs::write(path, contents)
+    }
+    #[inline]
+    fn append_file(&self, path: &Path, contents: &str) -> io::Result<()> {
+        #![allow(clippy::question_mark_used)]
+        use std::io::Write as _;
+        std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)?
+            .write_all(contents.as_bytes())
+    }
+    #[inline]
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        std::fs::read_dir(path)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|e| e.path()))
+                    .collect::<io::Result<Vec<_>>>()
+            })
+            .map(|mut paths| {
+                paths.sort();
+                paths
+            })
+    }
+    #[inline]
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        std::fs::create_dir_all(path)
+    }
+    #[inline]
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        std::fs::remove_file(path)
+    }
+    #[inline]
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+}
+
+/// A filesystem that only exists in memory: just a map from paths to contents, plus every directory those files are in (and any empty ones from `create_dir`).
+///
+/// Paths are compared after dropping any `.` in them, so `./notes.txt` and `notes.txt` are the same file, and `.` (or an empty path) is the directory everything else is in.
+/// Failures have the same `std::io::ErrorKind`s that `std::fs` gives on Linux.
+///
+/// Clones share the same files, so keep one around to check what a program did after running it against another.
+#[derive(Clone, Debug, Default)]
+pub struct FakeFileSystem(Rc<RefCell<Tree>>);
+
+/// Everything in a `FakeFileSystem`.
+#[derive(Clone, Debug, Default)]
+struct Tree {
+    /// Every file and its contents.
+    files: BTreeMap<PathBuf, String>,
+    /// Every directory other than a root, including every directory above each one.
+    dirs: BTreeSet<PathBuf>,
+}
+
+impl FakeFileSystem {
+    /// An empty filesystem.
+    #[inline(always)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Every file and its contents right now.
+    #[inline(always)]
+    #[must_use]
+    pub fn files(&self) -> BTreeMap<PathBuf, String> {
+        self.0.borrow().files.clone()
+    }
+}
+
+impl<P: Into<PathBuf>, S: Into<String>> FromIterator<(P, S)> for FakeFileSystem {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (P, S)>>(iter: I) -> Self {
+        let mut tree = Tree::default();
+        for (path, contents) in iter {
+            let file = normalize(&path.into());
+            tree.add_dirs_above(&file);
+            tree.files.insert(file, contents.into());
+        }
+        Self(Rc::new(RefCell::new(tree)))
+    }
+}
+
+impl Tree {
+    /// Whether there's a directory here.
+    fn is_dir(&self, path: &Path) -> bool {
+        path.parent().is_none() || self.dirs.contains(path)
+    }
+    /// Record every directory above a path.
+    fn add_dirs_above(&mut self, path: &Path) {
+        for dir in path.ancestors().skip(1) {
+            if dir.parent().is_some() {
+                self.dirs.insert(dir.to_path_buf());
+            }
+        }
+    }
+    /// The error `std::fs` would give if a file can't be written or removed, e.g. since it's a directory.
+    fn unwritable(&self, file: &Path, path: &Path) -> Option<io::Error> {
+        self.unreachable(file).or_else(|| {
+            self.is_dir(file)
+                .then(|| error(io::ErrorKind::IsADirectory, path, "is a directory"))
+        })
+    }
+    /// The error `std::fs` would give if it couldn't even get to a path, since something above it is missing or isn't a directory.
+    fn unreachable(&self, path: &Path) -> Option<io::Error> {
+        let mut above: Vec<&Path> = path.ancestors().skip(1).collect();
+        above.reverse();
+        above.into_iter().find_map(|dir| {
+            if self.files.contains_key(dir) {
+                Some(error(
+                    io::ErrorKind::NotADirectory,
+                    dir,
+                    "is not a directory",
+                ))
+            } else if self.is_dir(dir) {
+                None
+            } else {
+                Some(error(io::ErrorKind::NotFound, dir, "not found"))
+            }
+        })
+    }
+}
+
+/// The same path without any `.` in it; an empty path for `.` itself.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != std::path::Component::CurDir)
+        .collect()
+}
+
+/// An error of the same kind `std::fs` would give.
+fn error(kind: io::ErrorKind, path: &Path, why: &str) -> io::Error {
+    io::Error::new(kind, format!("{} {why}", path.display()))
+}
+
+impl FileSystem for FakeFileSystem {
+    #[inline]
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        let (tree, file) = (self.0.borrow(), normalize(path));
+        if let Some(e) = tree.unreachable(&file) {
+            return Err(e);
+        }
+        match tree.files.get(&file) {
+            Some(contents) => Ok(contents.clone()),
+            None if tree.is_dir(&file) => {
+                Err(error(io::ErrorKind::IsADirectory, path, "is a directory"))
+            }
+            None => Err(error(io::ErrorKind::NotFound, path, "not found")),
+        }
+    }
+    #[inline]
+    fn write_file(&self, path: &Path, contents: &str) -> io::Result<()> {
+        let (mut tree, file) = (self.0.borrow_mut(), normalize(path));
+        if let Some(e) = tree.unwritable(&file, path) {
+            return Err(e);
+        }
+        tree.files.insert(file, contents.to_owned());
+        Ok(())
+    }
+    #[inline]
+    fn append_file(&self, path: &Path, contents: &str) -> io::Result<()> {
+        let (mut tree, file) = (self.0.borrow_mut(), normalize(path));
+        if let Some(e) = tree.unwritable(&file, path) {
+            return Err(e);
+        }
+        tree.files.entry(file).or_default().push_str(contents);
+        Ok(())
+    }
+    #[inline]
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let (tree, dir) = (self.0.borrow(), normalize(path));
+        if let Some(e) = tree.unreachable(&dir) {
+            return Err(e);
+        }
+        if tree.files.contains_key(&dir) {
+            return Err(error(
+                io::ErrorKind::NotADirectory,
+                path,
+                "is not a directory",
+            ));
+        }
+        if !tree.is_dir(&dir) {
+            return Err(error(io::ErrorKind::NotFound, path, "not found"));
+        }
+        // Like `std::fs::read_dir`, name each child by joining it onto the path we were given.
+        let children: BTreeSet<PathBuf> = tree
+            .files
+            .keys()
+            .chain(tree.dirs.iter())
+            .filter(|child| child.parent() == Some(&dir))
+            .filter_map(|child| child.file_name())
+            .map(|name| path.join(name))
+            .collect();
+        Ok(children.into_iter().collect())
+    }
+    #[inline]
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        let (mut tree, dir) = (self.0.borrow_mut(), normalize(path));
+        if let Some(e) = tree.unreachable(&dir) {
+            if e.kind() != io::ErrorKind::NotFound {
+                return Err(e);
+            }
+        }
+        if tree.files.contains_key(&dir) {
+            return Err(error(io::ErrorKind::AlreadyExists, path, "already exists"));
+        }
+        tree.add_dirs_above(&dir);
+        if dir.parent().is_some() {
+            tree.dirs.insert(dir);
+        }
+        Ok(())
+    }
+    #[inline]
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let (mut tree, file) = (self.0.borrow_mut(), normalize(path));
+        if let Some(e) = tree.unwritable(&file, path) {
+            return Err(e);
+        }
+        tree.files
+            .remove(&file)
+            .map(drop)
+            .ok_or_else(|| error(io::ErrorKind::NotFound, path, "not found"))
+    }
+    #[inline]
+    fn exists(&self, path: &Path) -> bool {
+        let (tree, entry) = (self.0.borrow(), normalize(path));
+        tree.unreachable(&entry).is_none()
+            && (tree.files.contains_key(&entry) || tree.is_dir(&entry))
+    }
+}
+
+std::thread_local! {
+    /// Where file actions run right now.
+    static CURRENT: RefCell<Rc<dyn FileSystem>> = RefCell::new(Rc::new(RealFileSystem));
+}
+
+/// Puts the previous filesystem back once `Io::with_file_system` is done, even if its program panics.
+struct Restore(Rc<dyn FileSystem>);
+
+impl Drop for Restore {
+    #[inline]
+    fn drop(&mut self) {
+        let previous: Rc<dyn FileSystem> = Rc::clone(&self.0);
+        CURRENT.with(|current| current.replace(previous));
+    }
+}
+
+/// Run a file action against whichever filesystem is current.
+fn on_current<A: Clone + 'static, F: Fn(&dyn FileSystem) -> io::Result<A> + 'static>(
+    f: F,
+) -> IoResult<A> {
+    IoResult::attempt(move || {
+        let fs = CURRENT.with(|current| Rc::clone(&current.borrow()));
+        f(&*fs)
+    })
+}
+
+impl<A: 'static> Io<A> {
+    /// Run every file action in this program against another filesystem (e.g. a `FakeFileSystem`) instead of the real one.
+    #[inline]
+    #[must_use]
+    pub fn with_file_system<FS: FileSystem + 'static>(self, file_system: FS) -> Self {
+        let shared: Rc<dyn FileSystem> = Rc::new(file_system);
+        Self::new(move || {
+            let _restore = Restore(CURRENT.with(|current| current.replace(Rc::clone(&shared))));
+            self.clone().run()
+        })
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl<A: Clone + 'static, E: Clone + 'static> IoResult<A, E> {
+    /// Run every file action in this program against another filesystem (e.g. a `FakeFileSystem`) instead of the real one.
+    #[inline]
+    #[must_use]
+    pub fn with_file_system<FS: FileSystem + 'static>(self, file_system: FS) -> Self {
+        Self::new(self.run_io_result().with_file_system(file_system))
+    }
+}
+
+/// Reads a whole file as text.
+#[inline]
+#[must_use]
+pub fn read_file<P: AsRef<Path>>(path: P) -> IoResult<String> {
+    let file = path.as_ref().to_path_buf();
+    on_current(move |fs| fs.read_file(&file))
+}
+
+/// Replaces a file's contents, creating it if it doesn't exist.
+#[inline]
+#[must_use]
+pub fn write_file<P: AsRef<Path>, S: Into<String>>(path: P, contents: S) -> IoResult<()> {
+    let (file, text) = (path.as_ref().to_path_buf(), contents.into());
+    on_current(move |fs| fs.write_file(&file, &text))
+}
+
+/// Adds to the end of a file, creating it if it doesn't exist.
+#[inline]
+#[must_use]
+pub fn append_file<P: AsRef<Path>, S: Into<String>>(path: P, contents: S) -> IoResult<()> {
+    let (file, text) = (path.as_ref().to_path_buf(), contents.into());
+    on_current(move |fs| fs.append_file(&file, &text))
+}
+
+/// Lists everything directly inside a directory, sorted.
+#[inline]
+#[must_use]
+pub fn list_dir<P: AsRef<Path>>(path: P) -> IoResult<Vec<PathBuf>> {
+    let dir = path.as_ref().to_path_buf();
+    on_current(move |fs| fs.list_dir(&dir))
+}
+
+/// Makes a directory, along with any missing directories above it.
+#[inline]
+#[must_use]
+pub fn create_dir<P: AsRef<Path>>(path: P) -> IoResult<()> {
+    let dir = path.as_ref().to_path_buf();
+    on_current(move |fs| fs.create_dir(&dir))
+}
+
+/// Deletes a file.
+#[inline]
+#[must_use]
+pub fn remove_file<P: AsRef<Path>>(path: P) -> IoResult<()> {
+    let file = path.as_ref().to_path_buf();
+    on_current(move |fs| fs.remove_file(&file))
+}
+
+/// Checks whether a file or directory exists.
+#[inline]
+#[must_use]
+pub fn exists<P: AsRef<Path>>(path: P) -> IoResult<bool> {
+    let entry = path.as_ref().to_path_buf();
+    on_current(move |fs| Ok(fs.exists(&entry)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_file_system() {
+        let fake: FakeFileSystem = core::iter::once(("logs/old.txt", "ancient")).collect();
+        let setup = append_file("logs/new.txt", "one ")
+            >> |()| {
+                append_file("logs/new.txt", "two")
+                    >> |()| write_file("top.txt", "hi") >> |()| remove_file("logs/old.txt")
+            };
+        let check =
+            list_dir("logs") >> |listed| read_file("logs/new.txt") % move |read| (listed, read);
+        let program = setup >> move |()| check;
+
+        // Nothing happens until we run it...
+        let untouched = fake.files();
+        let on_fake = program.with_file_system(fake.clone());
+        assert_eq!(fake.files(), untouched);
+
+        // ...and then only in memory.
+        assert_eq!(
+            on_fake.run(),
+            Success((vec![PathBuf::from("logs/new.txt")], "one two".to_owned()))
+        );
+        assert_eq!(
+            fake.files(),
+            [
+                (PathBuf::from("logs/new.txt"), "one two".to_owned()),
+                (PathBuf::from("top.txt"), "hi".to_owned()),
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert_eq!(
+            exists("logs").with_file_system(fake.clone()).run(),
+            Success(true)
+        );
+        assert_eq!(
+            exists("logs/old.txt").with_file_system(fake).run(),
+            Success(false)
+        );
+    }
+
+    #[test]
+    fn fake_failures() {
+        let fake = FakeFileSystem::new();
+        assert!(matches!(
+            read_file("missing.txt")
+                .with_file_system(fake.clone())
+                .run(),
+            Failure(e) if e.kind() == io::ErrorKind::NotFound
+        ));
+        assert!(matches!(
+            remove_file("missing.txt")
+                .with_file_system(fake.clone())
+                .run(),
+            Failure(e) if e.kind() == io::ErrorKind::NotFound
+        ));
+        assert!(matches!(
+            list_dir("nowhere").with_file_system(fake.clone()).run(),
+            Failure(e) if e.kind() == io::ErrorKind::NotFound
+        ));
+
+        let with_file: FakeFileSystem = core::iter::once(("dir/file.txt", "")).collect();
+        assert!(matches!(
+            list_dir("dir/file.txt").with_file_system(with_file.clone()).run(),
+            Failure(e) if e.kind() == io::ErrorKind::NotADirectory
+        ));
+        assert!(matches!(
+            read_file("dir").with_file_system(with_file).run(),
+            Failure(e) if e.kind() == io::ErrorKind::IsADirectory
+        ));
+        assert!(matches!(
+            write_file("nowhere/file.txt", "").with_file_system(fake).run(),
+            Failure(e) if e.kind() == io::ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn fake_paths() {
+        let fake: FakeFileSystem = core::iter::once(("./notes.txt", "hi")).collect();
+        let program = create_dir("./empty")
+            >> |()| list_dir(".") >> |top| list_dir("empty") % move |inside| (top, inside);
+        assert_eq!(
+            program.with_file_system(fake.clone()).run(),
+            Success((
+                vec![PathBuf::from("./empty"), PathBuf::from("./notes.txt")],
+                vec![]
+            ))
+        );
+        assert_eq!(
+            read_file("notes.txt").with_file_system(fake.clone()).run(),
+            Success("hi".to_owned())
+        );
+        // Removing the last file in a directory leaves the directory behind.
+        let nested: FakeFileSystem = core::iter::once(("dir/file.txt", "")).collect();
+        assert_eq!(
+            (remove_file("dir/file.txt") >> |()| list_dir("dir"))
+                .with_file_system(nested.clone())
+                .run(),
+            Success(vec![])
+        );
+        assert_eq!(exists("dir").with_file_system(nested).run(), Success(true));
+        assert_eq!(exists("").with_file_system(fake).run(), Success(true));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn fake_matches_real() {
+        /// The same file actions, all somewhere under `root`, each described as a string.
+        fn steps(root: &Path) -> Vec<IoResult<String>> {
+            let at = |rest: &str| root.join(rest);
+            let done = |m: IoResult<()>| m % |()| "done".to_owned();
+            let listed = |m: IoResult<Vec<PathBuf>>| m % |paths| format!("{paths:?}");
+            let found = |m: IoResult<bool>| m % |b: bool| b.to_string();
+            vec![
+                done(create_dir(root)),
+                done(create_dir(at("dir"))),
+                done(create_dir(at("empty"))),
+                done(write_file(at("dir/file.txt"), "x")),
+                done(append_file(at("./dir/file.txt"), "y")),
+                read_file(at("dir/file.txt")),
+                listed(list_dir(root)),
+                listed(list_dir(at("empty"))),
+                listed(list_dir(at("./dir"))),
+                listed(list_dir(at("dir/file.txt"))),
+                listed(list_dir(at("missing"))),
+                read_file(at("dir")),
+                read_file(at("missing.txt")),
+                read_file(at("dir/file.txt/more")),
+                done(write_file(at("dir"), "")),
+                done(append_file(at("empty"), "")),
+                done(write_file(at("missing/file.txt"), "")),
+                done(remove_file(at("dir"))),
+                done(remove_file(at("missing.txt"))),
+                done(create_dir(at("dir/file.txt"))),
+                done(create_dir(at("dir/file.txt/sub"))),
+                found(exists(at("empty"))),
+                found(exists(at("./dir/file.txt"))),
+                found(exists(at("dir/file.txt/more"))),
+                done(remove_file(at("dir/file.txt"))),
+                listed(list_dir(at("dir"))),
+                found(exists(at("dir"))),
+            ]
+        }
+        /// What happened, keeping only the kind of any error, since the messages differ.
+        fn summarize(outcome: Hazard<String, IoError>) -> Result<String, io::ErrorKind> {
+            match outcome {
+                Success(s) => Ok(s),
+                Failure(e) => Err(e.kind()),
+            }
+        }
+
+        let root = std::env::temp_dir().join(format!("rsmonad-parity-{}", std::process::id()));
+        let fake = FakeFileSystem::new();
+        let on_real: Vec<_> = steps(&root)
+            .into_iter()
+            .map(|m| summarize(m.run()))
+            .collect();
+        let on_fake: Vec<_> = steps(&root)
+            .into_iter()
+            .map(|m| summarize(m.with_file_system(fake.clone()).run()))
+            .collect();
+        assert_eq!(std::fs::remove_dir_all(&root).ok(), Some(()));
+        assert_eq!(on_fake, on_real);
+    }
+
+    #[test]
+    fn real_file_system() {
+        let dir = std::env::temp_dir().join(format!("rsmonad-io-{}", std::process::id()));
+        assert_eq!(std::fs::create_dir_all(&dir).ok(), Some(()));
+        let file = dir.join("real.txt");
+        let program = write_file(&file, "a") >> {
+            let (appended, listed) = (file.clone(), dir.clone());
+            move |()| append_file(&appended, "b") >> move |()| list_dir(&listed)
+        };
+        assert!(!file.exists());
+        assert_eq!(program.run(), Success(vec![file.clone()]));
+        assert_eq!(read_file(&file).run(), Success("ab".to_owned()));
+        assert_eq!(remove_file(&file).run(), Success(()));
+        assert_eq!(std::fs::remove_dir(&dir).ok(), Some(()));
+    }
+}
@@ -6,63 +6,326 @@
 
 //! `Io` monad.
 
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
 use crate::prelude::*;
+use std::{ffi::OsStr, io, path::PathBuf, rc::Rc};
 
-/// Encodes the possibility of failure.
+/// A description of some interaction with the outside world that, once run, returns an `A`.
+///
+/// Under the hood, just a suspended function `() -> A`: building a program with `>>` does nothing at all,
+/// and every effect happens (in order, and again each time) only when it's `run`, usually once at the top of the program via `io_main!`.
+///
+/// Built-in actions (`put_line`, `read_file`, etc.) can fail, so they're `IoResult`s instead: `Io`s holding a `Hazard`.
 /// # Use
 /// ```rust
 /// use rsmonad::prelude::*;
-/// fn successor(x: u8) -> Maybe<u8> {
-///     x.checked_add(1).map_or(Nothing, Just)
-/// }
-/// assert_eq!(
-///     Just(3_u8) >> successor,
-///     Just(4),
-/// );
-/// assert_eq!(
-///     Nothing >> successor,
-///     Nothing,
-/// );
-/// assert_eq!(
-///     Just(255_u8) >> successor,
-///     Nothing,
-/// );
+/// let hello = Io::new(|| println!("Hello,")) >> |()| Io::new(|| println!("world!"));
+/// // Nothing has been printed yet: `hello` is just a value we can pass around, copy, or drop.
+/// hello.clone().run();
+/// hello.run(); // ...and again.
 /// ```
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, QuickCheck)]
-pub struct Io<A>(A);
+pub struct Io<A>(Rc<dyn Fn() -> A>);
+
 monad! {
     Io<A>:
 
-    fn consume(b) {
-        Io(b)
+    fn consume(a) {
+        Self::new(move || a.clone())
     }
 
     fn bind(self, f) {
-        f(self.0)
+        Io::new(move || (f.clone()((self.0)()).0)())
+    }
+
+    fn observe(m: Io<u64>) {
+        m.run()
+    }
+}
+
+impl<A> Io<A> {
+    /// Suspend a function that interacts with the outside world.
+    #[inline(always)]
+    pub fn new<F: Fn() -> A + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
     }
+    /// Perform every effect in this description, in order, and return its value.
+    /// Running the same `Io` twice performs its effects twice.
+    #[inline(always)]
+    pub fn run(self) -> A {
+        (self.0)()
+    }
+}
+
+/// Define `fn main` as a single `IoResult<()>` to run, exiting with code 1 (after printing why to `stderr`) if it fails.
+/// ```rust
+/// use rsmonad::prelude::*;
+/// io_main! {
+///     put_line("Hello,") >> |()| put_line("world!")
+/// }
+/// ```
+#[macro_export]
+macro_rules! io_main {
+    ($($program:tt)*) => {
+        fn main() {
+            #![allow(clippy::exit, clippy::print_stderr)]
+            let program: $crate::prelude::IoResult<()> = { $($program)* };
+            if let $crate::prelude::Failure(e) = program.run() {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+    };
 }
+pub use io_main;
 
-/// Reads a single line from `stdin`.
+impl<A> Clone for Io<A> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<A> core::fmt::Debug for Io<A> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Io(<function>)")
+    }
+}
+
+impl<A: quickcheck::Arbitrary> quickcheck::Arbitrary for Io<A> {
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        consume(A::arbitrary(g))
+    }
+}
+
+/// Command-line arguments, not including the program's own name.
+/// Fails with `InvalidData` if any of them isn't valid Unicode.
 #[must_use]
 #[inline(always)]
-pub fn get_line_stdin() -> Hazard<Io<String>, String> {
-    let mut s = String::new();
-    match std::io::stdin().read_line(&mut s) {
-        Ok(_) => Success(consume(s)),
-        Err(e) => Failure(e.to_string()),
-    }
+pub fn get_args() -> IoResult<List<String>> {
+    IoResult::attempt(|| {
+        std::env::args_os()
+            .skip(1)
+            .map(|arg| {
+                arg.into_string().map_err(|bad| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{} is not valid Unicode", bad.to_string_lossy()),
+                    )
+                })
+            })
+            .collect()
+    })
 }
 
-/// Prints without a newline.
+/// Look up an environment variable, if it's set.
+/// Fails with `InvalidData` if it's set to something that isn't valid Unicode.
 #[inline(always)]
-pub fn put<S: core::fmt::Display>(s: S) -> Io<()> {
-    #![allow(clippy::print_stdout)]
-    consume(print!("{s}"))
+pub fn get_env<K: AsRef<OsStr> + 'static>(key: K) -> IoResult<Maybe<String>> {
+    IoResult::attempt(move || match std::env::var(&key) {
+        Ok(value) => Ok(Just(value)),
+        Err(std::env::VarError::NotPresent) => Ok(Nothing),
+        Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+    })
 }
 
-/// Prints with a newline.
+/// Set an environment variable for this process and any it runs afterward.
+/// # Safety
+/// While the returned action runs, no other thread may read or write the environment
+/// (e.g. with `get_env`, `std::env`, a C library calling `getenv`, or by starting a subprocess),
+/// for the same reason `std::env::set_var` is `unsafe` as of edition 2024.
 #[inline(always)]
-pub fn put_line<S: core::fmt::Display>(s: S) -> Io<()> {
-    #![allow(clippy::print_stdout)]
-    consume(println!("{s}"))
+pub unsafe fn set_env<K: AsRef<OsStr> + 'static, V: AsRef<OsStr> + 'static>(
+    key: K,
+    value: V,
+) -> IoResult<()> {
+    IoResult::attempt(move || {
+        std::env::set_var(&key, &value);
+        Ok(())
+    })
+}
+
+/// The directory this process is running in.
+#[must_use]
+#[inline(always)]
+pub fn current_dir() -> IoResult<PathBuf> {
+    IoResult::attempt(std::env::current_dir)
+}
+
+/// Stop the whole process with this exit code, skipping everything after it.
+///
+/// Since it never returns, it fits anywhere in a program, whatever type it's expected to have.
+#[must_use]
+#[inline(always)]
+pub fn exit_with<A: Clone>(code: i32) -> IoResult<A> {
+    #![allow(clippy::exit)]
+    IoResult::attempt(move || std::process::exit(code))
+}
+
+/// Everything a finished subprocess left behind.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ProcessOutput {
+    /// Exit code, or `Nothing` if it was stopped by a signal.
+    pub status: Maybe<i32>,
+    /// Everything it wrote to standard output.
+    pub stdout: String,
+    /// Everything it wrote to standard error.
+    pub stderr: String,
+}
+
+impl ProcessOutput {
+    /// Whether it exited with code zero.
+    #[inline]
+    #[must_use]
+    pub fn success(&self) -> bool {
+        self.status == Just(0)
+    }
+}
+
+/// Run another program to completion, capturing its exit status and output.
+///
+/// Fails only if it couldn't be run at all; a nonzero exit code is still a `Success` (see `ProcessOutput::success`).
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(unix)]
+/// # {
+/// let greeting = run_process("echo", ["hello"]) % |out: ProcessOutput| out.stdout.trim().to_owned();
+/// assert_eq!(greeting.run(), Success("hello".to_owned()));
+/// # }
+/// ```
+#[inline(always)]
+pub fn run_process<
+    P: AsRef<OsStr> + 'static,
+    I: IntoIterator<Item = S> + Clone + 'static,
+    S: AsRef<OsStr>,
+>(
+    program: P,
+    args: I,
+) -> IoResult<ProcessOutput> {
+    IoResult::attempt(move || {
+        std::process::Command::new(&program)
+            .args(args.clone())
+            .output()
+            .map(|output| ProcessOutput {
+                status: output.status.code().map_or(Nothing, Just),
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn building_performs_no_effects() {
+        let calls = Rc::new(Cell::new(0_u8));
+        let counted = {
+            let counter = Rc::clone(&calls);
+            Io::new(move || counter.set(counter.get().saturating_add(1)))
+        };
+
+        // Describing, combining, and copying a program doesn't touch the outside world...
+        let program = counted.clone() >> move |()| counted;
+        let twice = program.clone() >> move |()| program;
+        let _unread = get_line_stdin() >> |_| put_line("never printed");
+        assert_eq!(calls.get(), 0);
+
+        // ...until it's run, which happens every time we ask.
+        let again = twice.clone();
+        twice.run();
+        assert_eq!(calls.get(), 4);
+        again.run();
+        assert_eq!(calls.get(), 8);
+    }
+
+    #[test]
+    fn effects_run_in_order() {
+        let log = Rc::new(core::cell::RefCell::new(vec![]));
+        let push = |x: u8| {
+            let shared = Rc::clone(&log);
+            Io::new(move || shared.borrow_mut().push(x))
+        };
+        let (one, two, three) = (push(1), push(2), push(3));
+        let program = one >> move |()| two >> move |()| three % |()| 42_u8;
+        assert!(log.borrow().is_empty());
+        assert_eq!(program.run(), 42);
+        assert_eq!(*log.borrow(), [1, 2, 3]);
+    }
+
+    /// Held by every test that reads or writes the environment, since `set_env` races with anything else doing so.
+    static ENVIRONMENT: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[test]
+    fn environment() {
+        let _only_us = ENVIRONMENT
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let key = "RSMONAD_IO_ENVIRONMENT_TEST";
+        let program = get_env(key)
+            >> move |before| {
+                // SAFETY: every test that reads or writes the environment holds `ENVIRONMENT` first.
+                let setting = unsafe { set_env(key, "set") };
+                setting >> move |()| get_env(key) % move |after| (before, after)
+            };
+        assert_eq!(std::env::var(key).ok(), None);
+        assert_eq!(program.run(), Success((Nothing, Just("set".to_owned()))));
+    }
+
+    #[test]
+    fn process_info() {
+        // Describing an exit (or anything else) doesn't do it.
+        let _never = exit_with::<()>(1);
+
+        let expected: Option<List<String>> = std::env::args_os()
+            .skip(1)
+            .map(|arg| arg.into_string().ok())
+            .collect();
+        match get_args().run() {
+            Success(args) => assert_eq!(Some(args), expected),
+            Failure(e) => {
+                assert_eq!(expected, None);
+                assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+            }
+        }
+        assert_eq!(
+            current_dir().run(),
+            Success(std::env::current_dir().unwrap_or_default())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn subprocesses() {
+        // Starting a process reads the environment.
+        let _only_us = ENVIRONMENT
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let echo = run_process("echo", ["hello", "world"]);
+        assert_eq!(
+            echo.run(),
+            Success(ProcessOutput {
+                status: Just(0),
+                stdout: "hello world\n".to_owned(),
+                stderr: String::new(),
+            })
+        );
+        // A nonzero exit code isn't a failure...
+        let statuses = run_process("true", Vec::<String>::new())
+            >> |t: ProcessOutput| {
+                run_process("false", Vec::<String>::new())
+                    % move |f: ProcessOutput| (t.success(), f.status)
+            };
+        assert_eq!(statuses.run(), Success((true, Just(1))));
+        // ...but not being able to run at all is.
+        assert!(matches!(
+            run_process("definitely-not-a-real-program", Vec::<String>::new()).run(),
+            Failure(e) if e.kind() == io::ErrorKind::NotFound
+        ));
+    }
 }
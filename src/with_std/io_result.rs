@@ -0,0 +1,331 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `IoResult` monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::{just_alternative, prelude::*};
+use core::cell::{Cell, OnceCell};
+use std::{io, rc::Rc};
+
+/// `Io` that can fail: once run, returns either an `A` or the reason it couldn't (by default an `IoError`).
+///
+/// Under the hood, just an `Io<Hazard<A, E>>`: `>>` runs each action in order until the first `Failure`, then skips everything after it.
+/// `catch` recovers from a failure, `on_error` cleans up after one, and `|` tries another action whenever the first one fails.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// use std::io::ErrorKind;
+/// let config = read_file("app.toml") | || read_file("default.toml");
+/// let program = config.catch(|e: IoError| {
+///     put_line_stderr(format!("no config ({e}), starting fresh")) % |()| String::new()
+/// }) >> |text: String| put_line(format!("{} bytes of config", text.len()));
+///
+/// let (outcome, console) = program.with_file_system(FakeFileSystem::new()).run_scripted(Vec::<String>::new());
+/// assert_eq!(outcome, Success(()));
+/// assert_eq!(console.stdout(), list!["0 bytes of config".to_owned()]);
+///
+/// // Errors keep their `std::io::ErrorKind`:
+/// let missing = read_file("missing.txt").with_file_system(FakeFileSystem::new()).run();
+/// assert!(matches!(missing, Failure(e) if e.kind() == ErrorKind::NotFound));
+/// ```
+pub struct IoResult<A: Clone, E: Clone = IoError>(Io<Hazard<A, E>>);
+
+monad! {
+    IoResult<A, E: Clone + 'static>:
+
+    fn consume(a) {
+        Self(consume(Success(a)))
+    }
+
+    fn bind(self, f) {
+        IoResult(self.0.bind(move |ha| match ha {
+            Success(a) => f(a).0,
+            Failure(e) => consume(Failure(e)),
+        }))
+    }
+
+    fn observe(m: IoResult<u64, u64>) {
+        m.run()
+    }
+}
+
+just_alternative! {
+    IoResult<A, E: Default + Clone + 'static>:
+
+    fn empty() {
+        Self::throw(E::default())
+    }
+
+    fn either(self, make_other) {
+        let pending = Rc::new((Cell::new(Some(make_other)), OnceCell::new()));
+        IoResult(self.0.bind(move |ha| match ha {
+            Success(a) => consume(Success(a)),
+            Failure(_) => pending.1.get_or_init(|| pending.0.take().map_or_else(empty, |f| f())).clone().0,
+        }))
+    }
+
+    // Only the last error survives, so these laws only hold up to which error we end up with.
+    fn observe(m: IoResult<u64, u64>) {
+        match m.run() {
+            Success(a) => Just(a),
+            Failure(_) => Nothing,
+        }
+    }
+}
+
+impl<A: Clone + 'static, E: Clone + 'static> MonadTrans<A, Io<()>> for IoResult<A, E> {
+    #[inline(always)]
+    fn lift(m: Io<A>) -> Self {
+        Self(m.fmap(Success))
+    }
+}
+
+impl<A: Clone, E: Clone> IoResult<A, E> {
+    /// Wrap an `Io` whose `Hazard` says whether it worked.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(io: Io<Hazard<A, E>>) -> Self {
+        Self(io)
+    }
+    /// Perform every action in this description, in order, until one fails, and return either the final value or the reason.
+    /// Running the same `IoResult` twice performs its effects twice.
+    #[inline(always)]
+    #[must_use]
+    pub fn run(self) -> Hazard<A, E> {
+        self.0.run()
+    }
+    /// Treat success and failure alike as an ordinary value of an `Io` that never fails.
+    #[inline(always)]
+    #[must_use]
+    pub fn run_io_result(self) -> Io<Hazard<A, E>> {
+        self.0
+    }
+}
+
+impl<A: Clone> IoResult<A> {
+    /// Suspend a function from the standard library, keeping the kind of any `std::io::Error` it returns.
+    #[inline(always)]
+    pub fn attempt<F: Fn() -> io::Result<A> + 'static>(f: F) -> Self {
+        Self(Io::new(move || match f() {
+            Ok(a) => Success(a),
+            Err(e) => Failure(e.into()),
+        }))
+    }
+}
+
+impl<A: Clone + 'static, E: Clone + 'static> IoResult<A, E> {
+    /// Fail, skipping every action after this one.
+    #[inline(always)]
+    pub fn throw(e: E) -> Self {
+        Self(consume(Failure(e)))
+    }
+    /// Recover from a failure by running another action with its reason.
+    #[inline(always)]
+    #[must_use]
+    pub fn catch<F: FnOnce(E) -> Self + Clone + 'static>(self, handler: F) -> Self {
+        Self(self.0.bind(move |ha| match ha {
+            Success(a) => consume(Success(a)),
+            Failure(e) => handler(e).0,
+        }))
+    }
+    /// Run some cleanup with the reason for a failure, then fail anyway (with the cleanup's own reason if it fails too).
+    #[inline(always)]
+    #[must_use]
+    pub fn on_error<F: FnOnce(E) -> IoResult<(), E> + Clone + 'static>(self, cleanup: F) -> Self {
+        self.catch(move |e| cleanup(e.clone()).bind(move |()| Self::throw(e)))
+    }
+}
+
+impl<X: Clone, E: Clone + 'static> MonadError<E> for IoResult<X, E> {
+    #[inline(always)]
+    fn throw<A: Clone + 'static>(e: E) -> IoResult<A, E> {
+        IoResult::throw(e)
+    }
+    #[inline(always)]
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> IoResult<A, E> + Clone + 'static>(
+        m: IoResult<A, E>,
+        handler: F,
+    ) -> IoResult<A, E> {
+        m.catch(handler)
+    }
+}
+
+impl<A: Clone, E: Clone> Clone for IoResult<A, E> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<A: Clone, E: Clone> core::fmt::Debug for IoResult<A, E> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("IoResult(<function>)")
+    }
+}
+
+impl<A: quickcheck::Arbitrary, E: quickcheck::Arbitrary> quickcheck::Arbitrary for IoResult<A, E> {
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self(Io::arbitrary(g))
+    }
+}
+
+/// Why an I/O action failed: a `std::io::Error`'s kind and message, but cheap to clone and compare.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct IoError {
+    /// What went wrong, as `std::io` categorizes it.
+    kind: io::ErrorKind,
+    /// What went wrong, in words.
+    message: String,
+}
+
+impl IoError {
+    /// An error of some kind with a message.
+    #[inline]
+    pub fn new<S: Into<String>>(kind: io::ErrorKind, message: S) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+    /// What went wrong, as `std::io` categorizes it.
+    #[inline(always)]
+    #[must_use]
+    pub const fn kind(&self) -> io::ErrorKind {
+        self.kind
+    }
+    /// What went wrong, in words.
+    #[inline(always)]
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Default for IoError {
+    /// What `empty` fails with: no alternative worked.
+    #[inline]
+    fn default() -> Self {
+        Self::new(io::ErrorKind::Other, "no alternative succeeded")
+    }
+}
+
+impl core::fmt::Display for IoError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl core::error::Error for IoError {}
+
+impl From<io::Error> for IoError {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        Self::new(e.kind(), e.to_string())
+    }
+}
+
+impl From<IoError> for io::Error {
+    #[inline]
+    fn from(e: IoError) -> Self {
+        Self::new(e.kind, e.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn failure_skips_later_actions() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let push = {
+            let shared = Rc::clone(&log);
+            move |x: u8| -> IoResult<(), u8> {
+                let pushing = Rc::clone(&shared);
+                lift(Io::new(move || pushing.borrow_mut().push(x)))
+            }
+        };
+        let (one, three, four) = (push(1), push(3), push(4));
+        let program = one >> move |()| IoResult::throw(2) >> move |()| three;
+        assert_eq!(program.clone().run(), Failure(2));
+        assert_eq!(*log.borrow(), [1]);
+
+        let cleaned_up = program.clone().on_error(move |_| four);
+        assert_eq!(cleaned_up.run(), Failure(2));
+        assert_eq!(*log.borrow(), [1, 1, 4]);
+
+        let recovered = program.catch(push);
+        assert_eq!(recovered.run(), Success(()));
+        assert_eq!(*log.borrow(), [1, 1, 4, 1, 2]);
+    }
+
+    #[test]
+    fn alternatives_run_until_one_succeeds() {
+        let attempts = Rc::new(RefCell::new(0_u8));
+        let attempt = |ok: bool| -> IoResult<bool> {
+            let counter = Rc::clone(&attempts);
+            IoResult::attempt(move || {
+                *counter.borrow_mut() += 1;
+                if ok {
+                    Ok(ok)
+                } else {
+                    Err(io::Error::new(io::ErrorKind::TimedOut, "too slow"))
+                }
+            })
+        };
+        let (first, second, third) = (attempt(false), attempt(false), attempt(true));
+        assert_eq!(
+            (first.clone() | move || second).run(),
+            Failure(IoError::new(io::ErrorKind::TimedOut, "too slow"))
+        );
+        assert_eq!(*attempts.borrow(), 2);
+        assert_eq!((third | move || first).run(), Success(true));
+        assert_eq!(*attempts.borrow(), 3);
+        assert_eq!(
+            empty::<IoResult<bool>, _>().run(),
+            Failure(IoError::default())
+        );
+    }
+
+    #[test]
+    fn retrying_forever_until_it_works() {
+        // The program is its own fallback, so it can only be built one attempt at a time.
+        fn retry(attempts: Rc<Cell<u8>>) -> IoResult<u8> {
+            let counter = Rc::clone(&attempts);
+            IoResult::attempt(move || {
+                counter.set(counter.get() + 1);
+                if counter.get() < 3 {
+                    Err(io::Error::new(io::ErrorKind::Interrupted, "try again"))
+                } else {
+                    Ok(counter.get())
+                }
+            }) | move || retry(attempts)
+        }
+        let attempts = Rc::new(Cell::new(0));
+        let program = retry(Rc::clone(&attempts));
+        assert_eq!(attempts.get(), 0);
+        assert_eq!(program.run(), Success(3));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn std_errors_round_trip() {
+        let original = io::Error::new(io::ErrorKind::PermissionDenied, "nope");
+        let converted = IoError::from(original);
+        assert_eq!(converted.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(converted.message(), "nope");
+        assert_eq!(
+            io::Error::from(converted).kind(),
+            io::ErrorKind::PermissionDenied
+        );
+    }
+}
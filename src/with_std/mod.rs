@@ -7,13 +7,19 @@
 //! Optional monads reliant on the standard library.
 
 mod blastdoor;
+mod console;
+mod file_system;
 mod io;
+mod io_result;
 mod list;
 mod orphans;
 mod unwind_monad;
 
 pub use blastdoor::*;
+pub use console::*;
+pub use file_system::*;
 pub use io::*;
+pub use io_result::*;
 pub use list::*;
 pub use orphans::*;
 pub use unwind_monad::*;
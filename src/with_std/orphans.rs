@@ -26,6 +26,17 @@ impl<A: Clone> Functor<A> for Vec<A> {
 }
 test_functor!(Vec<u64>);
 
+impl<X: Clone> FunctorKind for Vec<X> {
+    type Of<A: Clone> = Vec<A>;
+    #[inline(always)]
+    fn fmap_in<A: Clone + 'static, B: Clone + 'static, F: FnOnce(A) -> B + Clone + 'static>(
+        fa: Self::Of<A>,
+        f: F,
+    ) -> Self::Of<B> {
+        fa.fmap(f)
+    }
+}
+
 impl<A: Clone> Applicative<A> for Vec<A> {
     type Applicative<B: Clone> = Vec<B>;
     #[inline(always)]
@@ -33,10 +44,13 @@ impl<A: Clone> Applicative<A> for Vec<A> {
         vec![a]
     }
     #[inline(always)]
-    fn tie<F: FnOnce(A) -> B + Clone, B: Clone>(
+    fn tie<F: FnOnce(A) -> B + Clone + 'static, B: Clone + 'static>(
         self,
         af: Self::Applicative<F>,
-    ) -> Self::Applicative<B> {
+    ) -> Self::Applicative<B>
+    where
+        A: 'static,
+    {
         self.bind(move |a| af.bind(move |f| consume(f(a))))
     }
 }
@@ -49,7 +63,10 @@ impl<A: Clone> Alternative<A> for Vec<A> {
         vec![]
     }
     #[inline(always)]
-    fn either<F: FnOnce() -> Self>(mut self, make_other: F) -> Self {
+    fn either<F: FnOnce() -> Self + 'static>(mut self, make_other: F) -> Self
+    where
+        A: 'static,
+    {
         self.append(&mut make_other());
         self
     }
@@ -68,6 +85,25 @@ impl<A: Clone> Monad<A> for Vec<A> {
 }
 test_monad!(Vec<u64>);
 
+impl<X: Clone> MonadKind for Vec<X> {
+    type Of<A: Clone> = Vec<A>;
+    #[inline(always)]
+    fn consume_in<A: Clone + 'static>(a: A) -> Self::Of<A> {
+        consume(a)
+    }
+    #[inline(always)]
+    fn bind_in<
+        A: Clone + 'static,
+        B: Clone + 'static,
+        F: FnOnce(A) -> Self::Of<B> + Clone + 'static,
+    >(
+        m: Self::Of<A>,
+        f: F,
+    ) -> Self::Of<B> {
+        m.bind(f)
+    }
+}
+
 impl<A> Fold for Vec<A> {
     type Item = A;
 }
@@ -0,0 +1,98 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `Writer` monad.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+
+/// Accumulates a log of type `W` alongside each value, combining logs with `Monoid::combine` at every `>>`.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// fn half(x: u8) -> Writer<u8, SumU8> {
+///     Writer::tell(SumU8(1)) >> move |()| consume(x / 2)
+/// }
+/// assert_eq!(
+///     (half(100) >> half >> half).run_writer(),
+///     (12, SumU8(3)),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, QuickCheck)]
+pub struct Writer<A, W>(A, W);
+
+monad! {
+    Writer<A, W: Monoid + Clone + 'static>:
+
+    fn consume(a) {
+        Self(a, unit())
+    }
+
+    fn bind(self, f) {
+        let Writer(b, w) = f(self.0);
+        Writer(b, self.1.combine(w))
+    }
+
+    fn observe(m: Writer<u64, SumU8>) {
+        m.run_writer()
+    }
+}
+
+impl<A, W> Writer<A, W> {
+    /// Pair a value with a log.
+    #[inline(always)]
+    pub const fn new(a: A, w: W) -> Self {
+        Self(a, w)
+    }
+    /// Unwrap the final value and everything logged on the way there.
+    #[inline(always)]
+    pub fn run_writer(self) -> (A, W) {
+        (self.0, self.1)
+    }
+    /// Return the log so far alongside the value (and keep it in the log too).
+    #[inline(always)]
+    pub fn listen(self) -> Writer<(A, W), W>
+    where
+        W: Clone,
+    {
+        Writer((self.0, self.1.clone()), self.1)
+    }
+    /// Modify the log so far with some function.
+    #[inline(always)]
+    #[must_use]
+    pub fn censor<F: FnOnce(W) -> W>(self, f: F) -> Self {
+        Self(self.0, f(self.1))
+    }
+}
+
+impl<W> Writer<(), W> {
+    /// Log a value.
+    #[inline(always)]
+    pub const fn tell(w: W) -> Self {
+        Self((), w)
+    }
+}
+
+impl<A, F: FnOnce(W) -> W, W> Writer<(A, F), W> {
+    /// Modify the log with a function returned alongside the value.
+    #[inline(always)]
+    pub fn pass(self) -> Writer<A, W> {
+        let ((a, f), w) = (self.0, self.1);
+        Writer(a, f(w))
+    }
+}
+
+impl<X: Clone, W: Monoid + Clone + 'static> MonadWriter<W> for Writer<X, W> {
+    #[inline(always)]
+    fn tell(w: W) -> Writer<(), W> {
+        Writer::tell(w)
+    }
+    #[inline(always)]
+    fn listen<A: Clone + 'static>(m: Writer<A, W>) -> Writer<(A, W), W> {
+        m.listen()
+    }
+}
@@ -0,0 +1,249 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! `WriterT` monad transformer.
+
+#![allow(clippy::mismatching_type_param_order, clippy::missing_trait_methods)]
+
+use crate::prelude::*;
+
+/// `Writer` on top of any other monad `M` (written as e.g. `Vec<()>` for `Vec<_>`; see `MonadKind`).
+///
+/// Under the hood, just an `M` holding a value and its log `(A, W)`.
+/// # Use
+/// ```rust
+/// use rsmonad::prelude::*;
+/// # #[cfg(feature = "std")]
+/// # {
+/// // Every way to take one or two steps, counting how many steps we took:
+/// fn step(x: u8) -> WriterT<u8, SumU8, List<()>> {
+///     WriterT::tell(SumU8(1)) >> move |()| lift(list![x + 1, x + 2])
+/// }
+/// assert_eq!(
+///     (step(0) >> step).run_writer_t(),
+///     list![(2, SumU8(2)), (3, SumU8(2)), (3, SumU8(2)), (4, SumU8(2))],
+/// );
+/// # }
+/// ```
+pub struct WriterT<A: Clone, W: Clone, M: MonadKind>(M::Of<(A, W)>);
+
+monad! {
+    WriterT<A, W: Monoid + Clone + 'static, M: MonadKind + 'static>:
+
+    fn consume(a) {
+        Self(M::consume_in((a, unit())))
+    }
+
+    fn bind(self, f) {
+        WriterT(M::bind_in(self.0, move |(a, w)| {
+            M::bind_in(f(a).0, move |(b, v)| M::consume_in((b, w.combine(v))))
+        }))
+    }
+
+    fn observe(m: WriterT<u64, SumU8, Option<()>>) {
+        m.run_writer_t()
+    }
+}
+
+impl<A: Clone + 'static, W: Monoid + Clone + 'static, M: MonadKind> MonadTrans<A, M>
+    for WriterT<A, W, M>
+{
+    #[inline(always)]
+    fn lift(m: M::Of<A>) -> Self {
+        Self(M::bind_in(m, |a| M::consume_in((a, unit()))))
+    }
+}
+
+impl<A: Clone, W: Clone, M: MonadKind> WriterT<A, W, M> {
+    /// Wrap a value and its log inside the underlying monad.
+    #[inline(always)]
+    pub const fn new(m: M::Of<(A, W)>) -> Self {
+        Self(m)
+    }
+    /// Unwrap the underlying monad.
+    #[inline(always)]
+    pub fn run_writer_t(self) -> M::Of<(A, W)> {
+        self.0
+    }
+}
+
+impl<A: Clone + 'static, W: Clone + 'static, M: MonadKind> WriterT<A, W, M> {
+    /// Return the log so far alongside the value (and keep it in the log too).
+    #[inline(always)]
+    pub fn listen(self) -> WriterT<(A, W), W, M> {
+        WriterT(M::bind_in(self.0, |(a, w)| {
+            M::consume_in(((a, w.clone()), w))
+        }))
+    }
+    /// Modify the log so far with some function.
+    #[inline(always)]
+    #[must_use]
+    pub fn censor<F: FnOnce(W) -> W + Clone + 'static>(self, f: F) -> Self {
+        Self(M::bind_in(self.0, move |(a, w)| M::consume_in((a, f(w)))))
+    }
+}
+
+impl<W: Clone + 'static, M: MonadKind> WriterT<(), W, M> {
+    /// Log a value.
+    #[inline(always)]
+    pub fn tell(w: W) -> Self {
+        Self(M::consume_in(((), w)))
+    }
+}
+
+impl<X: Clone, W: Monoid + Clone + 'static, M: MonadKind + 'static> MonadWriter<W>
+    for WriterT<X, W, M>
+{
+    #[inline(always)]
+    fn tell(w: W) -> WriterT<(), W, M> {
+        WriterT::tell(w)
+    }
+    #[inline(always)]
+    fn listen<A: Clone + 'static>(m: WriterT<A, W, M>) -> WriterT<(A, W), W, M> {
+        m.listen()
+    }
+}
+
+impl<X: Clone, W: Monoid + Clone + 'static, S: Clone + 'static, M: MonadState<S> + 'static>
+    MonadState<S> for WriterT<X, W, M>
+{
+    #[inline(always)]
+    fn get() -> WriterT<S, W, M> {
+        WriterT::lift(M::get())
+    }
+    #[inline(always)]
+    fn put(s: S) -> WriterT<(), W, M> {
+        WriterT::lift(M::put(s))
+    }
+}
+
+impl<X: Clone, W: Monoid + Clone + 'static, R: Clone + 'static, M: MonadReader<R> + 'static>
+    MonadReader<R> for WriterT<X, W, M>
+{
+    #[inline(always)]
+    fn ask() -> WriterT<R, W, M> {
+        WriterT::lift(M::ask())
+    }
+    #[inline(always)]
+    fn local<A: Clone + 'static, F: Fn(&R) -> R + Clone + 'static>(
+        f: F,
+        m: WriterT<A, W, M>,
+    ) -> WriterT<A, W, M> {
+        WriterT(M::local(f, m.0))
+    }
+}
+
+impl<X: Clone, W: Monoid + Clone + 'static, E: Clone + 'static, M: MonadError<E> + 'static>
+    MonadError<E> for WriterT<X, W, M>
+{
+    #[inline(always)]
+    fn throw<A: Clone + 'static>(e: E) -> WriterT<A, W, M> {
+        WriterT::lift(M::throw(e))
+    }
+    #[inline(always)]
+    fn catch<A: Clone + 'static, F: FnOnce(E) -> WriterT<A, W, M> + Clone + 'static>(
+        m: WriterT<A, W, M>,
+        handler: F,
+    ) -> WriterT<A, W, M> {
+        WriterT(M::catch(m.0, move |e| handler(e).0))
+    }
+}
+
+impl<A: Clone, W: Clone, M: MonadKind> Clone for WriterT<A, W, M> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<A: Clone, W: Clone, M: MonadKind> core::fmt::Debug for WriterT<A, W, M>
+where
+    M::Of<(A, W)>: core::fmt::Debug,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("WriterT").field(&self.0).finish()
+    }
+}
+
+impl<A: Clone, W: Clone, M: MonadKind> PartialEq for WriterT<A, W, M>
+where
+    M::Of<(A, W)>: PartialEq,
+{
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<A: Clone + 'static, W: Clone + 'static, M: MonadKind + 'static> quickcheck::Arbitrary
+    for WriterT<A, W, M>
+where
+    M::Of<(A, W)>: quickcheck::Arbitrary,
+{
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self(M::Of::<(A, W)>::arbitrary(g))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod over_std {
+    //! Laws again, this time over some more interesting base monads.
+    #![allow(clippy::arithmetic_side_effects)]
+
+    use super::*;
+
+    mod over_list {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: WriterT<u64, SumU8, List<()>>) {
+                m.run_writer_t()
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: WriterT<u64, SumU8, List<()>>) {
+                m.run_writer_t()
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: WriterT<u64, SumU8, List<()>>) {
+                m.run_writer_t()
+            }
+        );
+    }
+
+    mod over_state {
+        use super::*;
+        crate::test_functor!(
+            fn observe(m: WriterT<u64, SumU8, State<(), u64>>, s: u64) {
+                m.run_writer_t().run_state(s)
+            }
+        );
+        crate::test_applicative!(
+            fn observe(m: WriterT<u64, SumU8, State<(), u64>>, s: u64) {
+                m.run_writer_t().run_state(s)
+            }
+        );
+        crate::test_monad!(
+            fn observe(m: WriterT<u64, SumU8, State<(), u64>>, s: u64) {
+                m.run_writer_t().run_state(s)
+            }
+        );
+    }
+
+    #[test]
+    fn listen_and_censor() {
+        type Logged = WriterT<u8, List<&'static str>, Io<()>>;
+        let program = WriterT::tell(list!["a"])
+            >> |()| Logged::lift(consume(1)) >> |x| WriterT::tell(list!["b"]) % move |()| x;
+        let listened = program.listen().censor(|w| w + list!["c"]);
+        assert_eq!(
+            listened.run_writer_t().run(),
+            ((1, list!["a", "b"]), list!["a", "b", "c"])
+        );
+    }
+}